@@ -1,6 +1,7 @@
 use chrono::Duration;
 use reqwest::multipart::{Form, Part};
 use reqwest::StatusCode;
+use simple_aws_s3::prelude::mime;
 use simple_aws_s3::{PostPresignedInfo, S3};
 
 // Before run this example, please replace s3 config below by your config.
@@ -18,10 +19,12 @@ async fn main() {
     let content = "Hello world";
 
     // Upload by Post Presigned
-    let PostPresignedInfo { upload_url, params } = s3
+    let PostPresignedInfo {
+        upload_url, params, ..
+    } = s3
         .generate_presigned_post(
             key.clone(),
-            "plain/text",
+            mime::TEXT_PLAIN,
             10485760,
             Duration::seconds(3600),
             None,
@@ -31,7 +34,9 @@ async fn main() {
     for (key, value) in params {
         form = form.text(key, value);
     }
-    let part = Part::text(content).mime_str("plain/text").unwrap();
+    let part = Part::text(content)
+        .mime_str(mime::TEXT_PLAIN.as_ref())
+        .unwrap();
     form = form.part("file", part);
     let res = reqwest::Client::new()
         .post(&upload_url)
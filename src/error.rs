@@ -1,11 +1,159 @@
+use std::convert::Infallible;
+use std::error::Error as StdError;
 use std::fmt;
+use std::io;
+use std::time::Duration;
 
+use chrono::{DateTime, Utc};
 use hmac::crypto_mac::InvalidKeyLength;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Response, StatusCode};
+
+/// Characters of a non-xml error body kept as the message of the error.
+const ERROR_BODY_LIMIT: usize = 1024;
+
+/// Error codes S3 returns when the credentials or the signature of a request are rejected.
+const CREDENTIAL_ERROR_CODES: &[&str] = &[
+    "InvalidAccessKeyId",
+    "SignatureDoesNotMatch",
+    "ExpiredToken",
+    "InvalidToken",
+    "TokenRefreshRequired",
+];
+
+/// Error codes S3 returns when requests are throttled.
+const THROTTLING_ERROR_CODES: &[&str] = &[
+    "SlowDown",
+    "Throttling",
+    "ThrottlingException",
+    "RequestLimitExceeded",
+    "TooManyRequests",
+    "TooManyRequestsException",
+];
 
 #[derive(Debug)]
 pub enum Error {
     SignError(String),
+    /// Any request error which is not a timeout or a connection error
     RequestError(reqwest::Error),
+    /// The request timed out
+    TimeoutError(reqwest::Error),
+    /// The endpoint could not be resolved or connected to
+    ConnectError(reqwest::Error),
+    /// S3 rejected the credentials or the signature of the request
+    CredentialError(Box<S3Error>),
+    /// `403 Forbidden`: the credentials are valid but not allowed to perform the request
+    AccessDenied(Box<S3Error>),
+    /// `404 Not Found`: the bucket or the object does not exist
+    NotFound(Box<S3Error>),
+    /// The client is misconfigured, e.g. an endpoint which is not a valid host
+    ConfigError(String),
+    InvalidKey(String),
+    /// A value given to build a request is invalid, e.g. a metadata value with a line break
+    InvalidInput(String),
+    /// A response body could not be parsed
+    XmlError(serde_xml_rs::Error),
+    /// A JSON document could not be serialized or deserialized
+    JsonError(serde_json::Error),
+    /// Reading or writing a local file failed
+    IoError(io::Error),
+    /// A response body is larger than the accepted `limit`, `length` is its declared length
+    BodyTooLarge {
+        limit: u64,
+        length: Option<u64>,
+    },
+    /// An upload session has not enough budget left for `requested` bytes
+    QuotaExceeded {
+        remaining: u64,
+        requested: u64,
+    },
+    /// An upload session expired at this date
+    SessionExpired(DateTime<Utc>),
+    /// A downloaded content does not match its checksum, `expected` and `actual` are hex SHA256
+    ChecksumMismatch {
+        expected: String,
+        actual: String,
+    },
+    /// Presigning was refused by a `PresignRateLimit`, until `retry_at`
+    RateLimited {
+        counter: String,
+        retry_at: DateTime<Utc>,
+    },
+    /// The client was shut down by `S3::shutdown`, and no longer sends requests
+    ShutDown,
+    /// Error response returned by S3
+    S3(Box<S3Error>),
+}
+
+impl Error {
+    /// Build the error matching a failed S3 response from its status and xml error body. A body
+    /// which is not xml, e.g. the html page of a proxy, is kept as the message.
+    pub async fn from_response(res: Response) -> Self {
+        let status = res.status();
+        let request_id = res
+            .headers()
+            .get("x-amz-request-id")
+            .and_then(|v| v.to_str().ok())
+            .map(String::from);
+        let retry_after = retry_after(res.headers(), Utc::now());
+        let body = match res.text().await {
+            Ok(body) => body,
+            Err(e) => return e.into(),
+        };
+
+        let mut err = if body.trim().is_empty() {
+            // HEAD responses and some proxies do not carry an error body
+            S3Error::from_status(status)
+        } else {
+            // Proxies and load balancers answer with html or plain text pages, keep their
+            // status so the error is still classified and retried
+            S3Error::from_xml(status, &body).unwrap_or_else(|_| S3Error {
+                message: body.trim().chars().take(ERROR_BODY_LIMIT).collect(),
+                ..S3Error::from_status(status)
+            })
+        };
+        if err.request_id.is_none() {
+            err.request_id = request_id;
+        }
+        err.retry_after = retry_after;
+
+        err.into()
+    }
+
+    /// Whether S3 asked to slow down: a `429`/`503` status or one of S3's throttling error codes.
+    #[inline]
+    pub fn is_throttling(&self) -> bool {
+        match self {
+            Self::S3(e) => e.is_throttling(),
+            _ => false,
+        }
+    }
+
+    /// Whether the request may succeed if sent again: timeouts, connection errors, throttling
+    /// and `5xx` errors of S3, also when reported in the body of a `200 OK`.
+    #[inline]
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::TimeoutError(_) | Self::ConnectError(_) => true,
+            Self::S3(e) => {
+                e.is_throttling()
+                    || e.status.is_server_error()
+                    || e.code == "RequestTimeout"
+                    || e.code == "InternalError"
+            }
+            _ => false,
+        }
+    }
+
+    /// Delay requested by S3 through the `Retry-After` header. Retries should wait at least this
+    /// long instead of only their own backoff.
+    #[inline]
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::S3(e) => e.retry_after,
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for Error {
@@ -13,11 +161,74 @@ impl fmt::Display for Error {
         let msg = match self {
             Self::SignError(msg) => format!("Sign Error: {}", msg),
             Self::RequestError(e) => format!("Execute Request Error: {}", e),
+            Self::TimeoutError(e) => format!("Timeout Error: {}", e),
+            Self::ConnectError(e) => format!("Connect Error: {}", e),
+            Self::CredentialError(e) => format!("Credential Error: {}", e),
+            Self::AccessDenied(e) => format!("Access Denied: {}", e),
+            Self::NotFound(e) => format!("Not Found: {}", e),
+            Self::ConfigError(msg) => format!("Config Error: {}", msg),
+            Self::InvalidKey(msg) => format!("Invalid Key: {}", msg),
+            Self::InvalidInput(msg) => format!("Invalid Input: {}", msg),
+            Self::XmlError(e) => format!("Xml Error: {}", e),
+            Self::JsonError(e) => format!("Json Error: {}", e),
+            Self::IoError(e) => format!("Io Error: {}", e),
+            Self::BodyTooLarge { limit, length } => match length {
+                Some(length) => format!("Body Too Large: {} bytes, limit is {}", length, limit),
+                None => format!("Body Too Large: more than the limit of {} bytes", limit),
+            },
+            Self::QuotaExceeded {
+                remaining,
+                requested,
+            } => format!(
+                "Quota Exceeded: {} bytes requested, {} bytes remaining",
+                requested, remaining
+            ),
+            Self::SessionExpired(date) => format!("Session Expired: at {}", date),
+            Self::ChecksumMismatch { expected, actual } => format!(
+                "Checksum Mismatch: expected SHA256 {}, got {}",
+                expected, actual
+            ),
+            Self::RateLimited { counter, retry_at } => format!(
+                "Rate Limited: too many presigns of {:?}, retry at {}",
+                counter, retry_at
+            ),
+            Self::ShutDown => "Shut Down: the client no longer sends requests".to_string(),
+            Self::S3(e) => format!("S3 Error: {}", e),
         };
         write!(f, "{}", msg)
     }
 }
 
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::RequestError(e) | Self::TimeoutError(e) | Self::ConnectError(e) => Some(e),
+            Self::CredentialError(e) | Self::AccessDenied(e) | Self::NotFound(e) | Self::S3(e) => {
+                Some(e.as_ref())
+            }
+            Self::XmlError(e) => Some(e),
+            Self::JsonError(e) => Some(e),
+            Self::IoError(e) => Some(e),
+            Self::SignError(_)
+            | Self::ConfigError(_)
+            | Self::InvalidKey(_)
+            | Self::InvalidInput(_)
+            | Self::BodyTooLarge { .. }
+            | Self::QuotaExceeded { .. }
+            | Self::SessionExpired(_)
+            | Self::ChecksumMismatch { .. }
+            | Self::RateLimited { .. }
+            | Self::ShutDown => None,
+        }
+    }
+}
+
+impl From<Infallible> for Error {
+    fn from(e: Infallible) -> Self {
+        match e {}
+    }
+}
+
 impl From<InvalidKeyLength> for Error {
     fn from(e: InvalidKeyLength) -> Self {
         Self::SignError(e.to_string())
@@ -26,6 +237,218 @@ impl From<InvalidKeyLength> for Error {
 
 impl From<reqwest::Error> for Error {
     fn from(e: reqwest::Error) -> Self {
-        Self::RequestError(e)
+        if e.is_timeout() {
+            Self::TimeoutError(e)
+        } else if e.is_connect() {
+            Self::ConnectError(e)
+        } else {
+            Self::RequestError(e)
+        }
     }
 }
+
+impl From<serde_xml_rs::Error> for Error {
+    fn from(e: serde_xml_rs::Error) -> Self {
+        Self::XmlError(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Self::JsonError(e)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::IoError(e)
+    }
+}
+
+impl From<S3Error> for Error {
+    fn from(e: S3Error) -> Self {
+        if CREDENTIAL_ERROR_CODES.contains(&e.code.as_str()) {
+            Self::CredentialError(Box::new(e))
+        } else if e.status == StatusCode::FORBIDDEN {
+            Self::AccessDenied(Box::new(e))
+        } else if e.status == StatusCode::NOT_FOUND {
+            Self::NotFound(Box::new(e))
+        } else {
+            Self::S3(Box::new(e))
+        }
+    }
+}
+
+/// Error returned by S3.
+///
+/// Ref: https://docs.aws.amazon.com/AmazonS3/latest/API/ErrorResponses.html
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::error::{Error, S3Error};
+/// use simple_aws_s3::prelude::reqwest::StatusCode;
+///
+/// let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <Error>
+///   <Code>NoSuchKey</Code>
+///   <Message>The resource you requested does not exist</Message>
+///   <Resource>/mybucket/myfoto.jpg</Resource>
+///   <RequestId>4442587FB7D0A2F9</RequestId>
+/// </Error>"#;
+///
+/// let err = S3Error::from_xml(StatusCode::NOT_FOUND, body).unwrap();
+/// assert_eq!(err.code, "NoSuchKey");
+/// assert_eq!(err.request_id.as_deref(), Some("4442587FB7D0A2F9"));
+/// assert!(matches!(Error::from(err), Error::NotFound(_)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct S3Error {
+    pub status: StatusCode,
+    pub code: String,
+    pub message: String,
+    pub resource: Option<String>,
+    pub request_id: Option<String>,
+    pub host_id: Option<String>,
+    /// Parsed `Retry-After` header of the response
+    pub retry_after: Option<Duration>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ErrorBody {
+    code: String,
+    message: Option<String>,
+    resource: Option<String>,
+    request_id: Option<String>,
+    host_id: Option<String>,
+}
+
+impl S3Error {
+    #[inline]
+    pub fn from_xml(status: StatusCode, body: &str) -> Result<Self, Error> {
+        let body: ErrorBody = serde_xml_rs::from_str(body)?;
+        Ok(Self {
+            status,
+            code: body.code,
+            message: body.message.unwrap_or_default(),
+            resource: body.resource,
+            request_id: body.request_id,
+            host_id: body.host_id,
+            retry_after: None,
+        })
+    }
+
+    /// Error reported in the body of a `200 OK` response. S3 sends the status of long operations
+    /// (copies, multipart completions) before their end, and reports a failure in the body.
+    ///
+    /// Example:
+    /// ```rust
+    /// use simple_aws_s3::error::S3Error;
+    ///
+    /// let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+    /// <Error><Code>InternalError</Code><Message>We encountered an internal error. Please try again.</Message></Error>"#;
+    /// assert_eq!(S3Error::from_ok_body(body).unwrap().code, "InternalError");
+    ///
+    /// let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+    /// <CopyObjectResult><ETag>"9b2cf535f27731c974343645a3985328"</ETag></CopyObjectResult>"#;
+    /// assert!(S3Error::from_ok_body(body).is_none());
+    /// ```
+    #[inline]
+    pub fn from_ok_body(body: &str) -> Option<Self> {
+        if xml_root(body)? != "Error" {
+            return None;
+        }
+        let status = StatusCode::OK;
+        Some(Self::from_xml(status, body).unwrap_or_else(|_| Self {
+            message: body.to_string(),
+            ..Self::from_status(status)
+        }))
+    }
+
+    /// Error of a response without body, the code is derived from the status, e.g. `NotFound`.
+    #[inline]
+    pub fn from_status(status: StatusCode) -> Self {
+        let reason = status.canonical_reason().unwrap_or("Unknown");
+        Self {
+            status,
+            code: reason.replace(' ', ""),
+            message: reason.to_string(),
+            resource: None,
+            request_id: None,
+            host_id: None,
+            retry_after: None,
+        }
+    }
+
+    #[inline]
+    pub fn is_throttling(&self) -> bool {
+        self.status == StatusCode::TOO_MANY_REQUESTS
+            || self.status == StatusCode::SERVICE_UNAVAILABLE
+            || THROTTLING_ERROR_CODES.contains(&self.code.as_str())
+    }
+}
+
+/// Name of the root element of an xml document.
+#[inline]
+fn xml_root(body: &str) -> Option<&str> {
+    let mut rest = body.trim_start();
+    // Skip the declaration and comments
+    while let Some(prolog) = rest
+        .strip_prefix("<?")
+        .or_else(|| rest.strip_prefix("<!--"))
+    {
+        let end = prolog.find('>')?;
+        rest = prolog[end + 1..].trim_start();
+    }
+    let element = rest.strip_prefix('<')?;
+    let end = element
+        .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .unwrap_or(element.len());
+    Some(&element[..end])
+}
+
+impl fmt::Display for S3Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}): {}", self.code, self.status, self.message)?;
+        if let Some(request_id) = &self.request_id {
+            write!(f, " [request id: {}]", request_id)?;
+        }
+        Ok(())
+    }
+}
+
+impl StdError for S3Error {}
+
+/// Parse a `Retry-After` header, given either in seconds or as an http date.
+///
+/// Example:
+/// ```rust
+/// use std::time::Duration;
+/// use chrono::{TimeZone, Utc};
+/// use simple_aws_s3::error::retry_after;
+/// use simple_aws_s3::prelude::reqwest::header::{HeaderMap, RETRY_AFTER};
+///
+/// let now = Utc.ymd(2015, 10, 21).and_hms(7, 28, 0);
+/// let mut headers = HeaderMap::new();
+///
+/// headers.insert(RETRY_AFTER, "120".parse().unwrap());
+/// assert_eq!(retry_after(&headers, now), Some(Duration::from_secs(120)));
+///
+/// headers.insert(RETRY_AFTER, "Wed, 21 Oct 2015 07:28:30 GMT".parse().unwrap());
+/// assert_eq!(retry_after(&headers, now), Some(Duration::from_secs(30)));
+/// ```
+#[inline]
+pub fn retry_after(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        date.with_timezone(&Utc)
+            .signed_duration_since(now)
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
@@ -4,17 +4,63 @@
 //! This package is developing while waiting the fully supported from [aws-sdk](https://github.com/awslabs/aws-sdk-rust) from Amazon.
 //! ### Features:
 //!
-//! + Post Presigned (Upload from browser)
+//! + Post Presigned (Upload from browser, with per tenant guardrails and upload sessions with a size budget)
 //! + Get Presigned (Download from browser)
+//! + Put Presigned (Upload from browser, with signed headers)
+//! + Default and maximum expiry of every presigned url and post
+//! + Rate limit of the presigns per key or prefix, with a pluggable counter store
+//! + Presign Key Rotation (revoke every url and post presigned before a leak)
 //! + Bucket Operations:
+//!     + Create Bucket, and converge its settings to a declared configuration
+//!     + Versioning, Default Encryption, Public Access Block, CORS and Lifecycle rules
+//!     + Bucket ACL (get and put, with typed grants)
+//!     + Bucket Ownership Controls
+//!     + Request Payment (requester pays)
+//!     + Object Lock Configuration (default retention, and retention of presigned posts)
+//!     + List Objects (V2, streamed, with glob, date and size filters, sharded in parallel)
+//!     + List Object Versions, Multipart Uploads and Parts, paginated like the objects
+//!     + Folders (list, create and size of prefixes)
+//!     + Storage Usage (size and object count, grouped by prefix)
+//!     + Watch Prefix (poll a prefix for created, modified and deleted objects)
+//!     + Audit Prefix (check size, ETag, storage class and checksum of objects)
+//!     + Delete Objects (batch delete, and expiry of objects older than a date)
 //! + Object Operations:
 //!     + Head Object (Retrieve Information of an Object)
+//!     + Verify Client Uploads (size, content type, checksums and metadata, with content
+//!       inspectors and quarantine)
+//!     + Get Object
+//!     + Put Object (server side upload with a signed payload)
+//!     + Stream Object to an http response (ranges, headers and conditional requests passed
+//!       through)
+//!     + Get/Put Object as JSON
 //!     + Delete Object
+//!     + Copy Object (with canned ACL and grants)
+//!     + Multipart Upload (and upload from a `Stream` of bytes)
+//!     + Deduplicated Upload (skipped when the object already has the same SHA256)
+//!     + Content-Addressed Storage (immutable objects stored under their SHA256)
+//!     + Temporary Objects (deleted when their guard is dropped)
+//!     + Seekable Reader (`AsyncRead` + `AsyncSeek` over ranged gets)
+//!     + Resumable Download (progress saved to a state file)
+//!     + Object Lambda Access Points
+//!     + Torrent of an Object
+//! + Transfer a prefix to another client, e.g. from AWS to another provider
+//! + Server Access Log parser
+//! + Inventory report reader
+//! + Scoped Client (keys jailed to a prefix, e.g. per tenant)
+//! + Anonymous Client (unsigned requests to public buckets)
+//! + Follows redirects to the region of the bucket
+//! + Default ACL, cache control, encryption and metadata of every upload
+//! + Retries with backoff, bounded by a client level retry budget
+//! + Audit trail of every operation to a pluggable sink
+//! + Dry-run mode, mutating operations are signed but not sent
+//! + Graceful shutdown, draining the transfers in flight and aborting abandoned uploads
 //!
 //! ### Examples:
+//! ```rust,ignore
 //! use chrono::Duration;
 //! use reqwest::multipart::{Form, Part};
 //! use reqwest::StatusCode;
+//! use simple_aws_s3::prelude::mime;
 //! use simple_aws_s3::{PostPresignedInfo, S3};
 //!
 //! // Before run this example, please replace s3 config below by your config.
@@ -32,10 +78,12 @@
 //!     let content = "Hello world";
 //!
 //!     // Upload by Post Presigned
-//!     let PostPresignedInfo { upload_url, params } = s3
+//!     let PostPresignedInfo {
+//!         upload_url, params, ..
+//!     } = s3
 //!         .generate_presigned_post(
 //!             key.clone(),
-//!             "plain/text",
+//!             mime::TEXT_PLAIN,
 //!             10485760,
 //!             Duration::seconds(3600),
 //!             None,
@@ -45,7 +93,7 @@
 //!     for (key, value) in params {
 //!         form = form.text(key, value);
 //!     }
-//!     let part = Part::text(content).mime_str("plain/text").unwrap();
+//!     let part = Part::text(content).mime_str(mime::TEXT_PLAIN.as_ref()).unwrap();
 //!     form = form.part("file", part);
 //!     let res = reqwest::Client::new()
 //!         .post(&upload_url)
@@ -65,29 +113,129 @@
 //!     assert_eq!(res.status(), StatusCode::OK);
 //!     assert_eq!(res.text().await.unwrap(), content);
 //! }
-//! + [Upload/Download](https://github.com/cptrodgers/simple-aws-s3/tree/master/examples)
 //! ```
+//! + [Upload/Download](https://github.com/cptrodgers/simple-aws-s3/tree/master/examples)
 
 #[macro_use]
 extern crate serde;
 
 pub mod error;
 pub mod s3;
+pub mod s3_access_log;
+pub mod s3_acl;
+pub mod s3_audit;
+pub mod s3_audit_trail;
+pub mod s3_body;
+pub mod s3_bucket_setup;
+pub mod s3_cache;
+pub mod s3_cas;
+pub mod s3_client;
 pub mod s3_constant;
+pub mod s3_cors;
+pub mod s3_dedup;
+pub mod s3_delete;
+pub mod s3_download;
+pub mod s3_dry_run;
+pub mod s3_encryption;
+pub mod s3_folder;
+pub mod s3_inspect;
+pub mod s3_inventory;
+pub mod s3_json;
+pub mod s3_key;
+pub mod s3_key_rotation;
+pub mod s3_lifecycle;
+pub mod s3_list;
+pub mod s3_mime;
+pub mod s3_multipart;
+pub mod s3_object;
+pub mod s3_object_lock;
+pub mod s3_output;
+pub mod s3_ownership;
+pub mod s3_paginator;
+pub mod s3_parallel_list;
+pub mod s3_partition;
 pub mod s3_post_policy;
+pub mod s3_presign_policy;
+pub mod s3_presign_rate_limit;
+pub mod s3_proxy;
+pub mod s3_public_access_block;
+pub mod s3_region;
+pub mod s3_request_options;
+pub mod s3_request_payment;
+pub mod s3_retry;
+pub mod s3_scope;
+pub mod s3_shutdown;
 pub mod s3_signer;
 pub mod s3_string_to_sign;
+pub mod s3_temp_object;
+pub mod s3_tenant;
+pub mod s3_torrent;
+pub mod s3_transfer;
+pub mod s3_upload_session;
+pub mod s3_usage;
+pub mod s3_verify;
+pub mod s3_versioning;
+pub mod s3_watch;
 
 // Export as main level
 pub use s3::*;
+pub use s3_access_log::*;
+pub use s3_acl::*;
+pub use s3_audit::*;
+pub use s3_audit_trail::*;
+pub use s3_body::*;
+pub use s3_bucket_setup::*;
+pub use s3_cache::*;
+pub use s3_cas::*;
 pub use s3_constant::*;
+pub use s3_cors::*;
+pub use s3_dedup::*;
+pub use s3_delete::*;
+pub use s3_download::*;
+pub use s3_dry_run::*;
+pub use s3_encryption::*;
+pub use s3_inspect::*;
+pub use s3_inventory::*;
+pub use s3_key::*;
+pub use s3_key_rotation::*;
+pub use s3_lifecycle::*;
+pub use s3_list::*;
+pub use s3_mime::*;
+pub use s3_multipart::*;
+pub use s3_object::*;
+pub use s3_object_lock::*;
+pub use s3_output::*;
+pub use s3_ownership::*;
+pub use s3_paginator::*;
+pub use s3_partition::*;
 pub use s3_post_policy::*;
+pub use s3_presign_policy::*;
+pub use s3_presign_rate_limit::*;
+pub use s3_proxy::*;
+pub use s3_public_access_block::*;
+pub use s3_region::*;
+pub use s3_request_options::*;
+pub use s3_request_payment::*;
+pub use s3_retry::*;
+pub use s3_scope::*;
+pub use s3_shutdown::*;
 pub use s3_signer::*;
 pub use s3_string_to_sign::*;
+pub use s3_temp_object::*;
+pub use s3_tenant::*;
+pub use s3_transfer::*;
+pub use s3_upload_session::*;
+pub use s3_usage::*;
+pub use s3_verify::*;
+pub use s3_versioning::*;
+pub use s3_watch::*;
 
 // Export dependencies
 pub mod prelude {
+    pub use bytes;
     pub use hmac;
+    pub use http;
+    pub use mime;
     pub use reqwest;
     pub use sha2;
 }
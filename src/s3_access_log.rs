@@ -0,0 +1,206 @@
+use std::borrow::Cow;
+
+use chrono::{DateTime, Utc};
+use percent_encoding::percent_decode_str;
+
+use crate::error::Error;
+
+/// A record of S3 server access logs.
+///
+/// Fields logged as `-` are `None`. Fields added to the format after a log was written are
+/// `None` as well, and fields unknown to this version are ignored.
+///
+/// Ref: https://docs.aws.amazon.com/AmazonS3/latest/userguide/LogFormat.html
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::AccessLogRecord;
+///
+/// let line = r#"79a59df900b949e55d96a1e698fbacedfd6e09d98eacf8f8d5218e7cd47ef2be DOC-EXAMPLE-BUCKET1 [06/Feb/2019:00:00:38 +0000] 192.0.2.3 79a59df900b949e55d96a1e698fbacedfd6e09d98eacf8f8d5218e7cd47ef2be 3E57427F3EXAMPLE REST.GET.OBJECT photos/my%20cat.jpg "GET /DOC-EXAMPLE-BUCKET1/photos/my%20cat.jpg HTTP/1.1" 200 - 113 113 7 6 "-" "S3Console/0.4 (compatible; \"beta\" build)" - s9lzHYrFp76ZVxRcpX9+5cjAnEH2ROuNkd2BHfIa6UkFVdtjf5mKR3/eTPFvsiP/XV/VLi31234= SigV4 ECDHE-RSA-AES128-GCM-SHA256 AuthHeader DOC-EXAMPLE-BUCKET1.s3.us-west-1.amazonaws.com TLSV1.2 - Yes"#;
+///
+/// let record = AccessLogRecord::parse(line).unwrap();
+/// assert_eq!(record.bucket, "DOC-EXAMPLE-BUCKET1");
+/// assert_eq!(record.time.to_rfc3339(), "2019-02-06T00:00:38+00:00");
+/// assert_eq!(record.operation, "REST.GET.OBJECT");
+/// assert_eq!(record.decoded_key().as_deref(), Some("photos/my cat.jpg"));
+/// assert_eq!(record.http_status, Some(200));
+/// assert_eq!(record.error_code, None);
+/// assert_eq!(record.bytes_sent, Some(113));
+/// assert_eq!(record.referer, None);
+/// assert_eq!(record.user_agent.as_deref(), Some(r#"S3Console/0.4 (compatible; \"beta\" build)"#));
+/// assert_eq!(record.tls_version.as_deref(), Some("TLSV1.2"));
+/// assert_eq!(record.access_point_arn, None);
+/// assert_eq!(record.acl_required, Some(true));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessLogRecord {
+    /// Canonical user id of the owner of the bucket
+    pub bucket_owner: String,
+    pub bucket: String,
+    /// When the request was received
+    pub time: DateTime<Utc>,
+    pub remote_ip: Option<String>,
+    /// Canonical user id or IAM arn of the requester, `None` for anonymous requests
+    pub requester: Option<String>,
+    pub request_id: String,
+    /// e.g. `REST.GET.OBJECT`
+    pub operation: String,
+    /// Url encoded key of the request, see `decoded_key`
+    pub key: Option<String>,
+    /// Request line, e.g. `GET /bucket/key HTTP/1.1`
+    pub request_uri: Option<String>,
+    pub http_status: Option<u16>,
+    /// Error code of S3, e.g. `NoSuchKey`
+    pub error_code: Option<String>,
+    /// Bytes of the response body
+    pub bytes_sent: Option<u64>,
+    pub object_size: Option<u64>,
+    /// Milliseconds from the reception of the request to the last byte of the response
+    pub total_time: Option<u64>,
+    /// Milliseconds S3 spent processing the request
+    pub turn_around_time: Option<u64>,
+    pub referer: Option<String>,
+    pub user_agent: Option<String>,
+    pub version_id: Option<String>,
+    pub host_id: Option<String>,
+    /// `SigV2` or `SigV4`, `None` for unauthenticated requests
+    pub signature_version: Option<String>,
+    pub cipher_suite: Option<String>,
+    /// `AuthHeader` or `QueryString`
+    pub authentication_type: Option<String>,
+    pub host_header: Option<String>,
+    pub tls_version: Option<String>,
+    pub access_point_arn: Option<String>,
+    /// Whether an ACL was required to authorize the request
+    pub acl_required: Option<bool>,
+}
+
+impl AccessLogRecord {
+    /// Parse a line of an access log.
+    #[inline]
+    pub fn parse(line: &str) -> Result<Self, Error> {
+        let fields = split_fields(line.trim_end())?;
+        let field = |i: usize| {
+            fields
+                .get(i)
+                .filter(|value| **value != "-")
+                .map(|value| value.to_string())
+        };
+        let required = |i: usize, name: &str| {
+            field(i).ok_or_else(|| {
+                Error::InvalidInput(format!("Access log record without {}: {:?}", name, line))
+            })
+        };
+        let number = |i: usize| field(i).and_then(|value| value.parse().ok());
+
+        let time = required(2, "time")?;
+        let time = DateTime::parse_from_str(&time, "%d/%b/%Y:%H:%M:%S %z")
+            .map_err(|e| Error::InvalidInput(format!("Invalid access log time {:?}: {}", time, e)))?
+            .with_timezone(&Utc);
+
+        Ok(Self {
+            bucket_owner: required(0, "bucket owner")?,
+            bucket: required(1, "bucket")?,
+            time,
+            remote_ip: field(3),
+            requester: field(4),
+            request_id: required(5, "request id")?,
+            operation: required(6, "operation")?,
+            key: field(7),
+            request_uri: field(8),
+            http_status: field(9).and_then(|value| value.parse().ok()),
+            error_code: field(10),
+            bytes_sent: number(11),
+            object_size: number(12),
+            total_time: number(13),
+            turn_around_time: number(14),
+            referer: field(15),
+            user_agent: field(16),
+            version_id: field(17),
+            host_id: field(18),
+            signature_version: field(19),
+            cipher_suite: field(20),
+            authentication_type: field(21),
+            host_header: field(22),
+            tls_version: field(23),
+            access_point_arn: field(24),
+            acl_required: field(25).map(|value| value == "Yes"),
+        })
+    }
+
+    /// Key of the request, url decoded.
+    #[inline]
+    pub fn decoded_key(&self) -> Option<Cow<'_, str>> {
+        let key = self.key.as_deref()?;
+        Some(percent_decode_str(key).decode_utf8_lossy())
+    }
+}
+
+/// Parse the records of an access log file, skipping its blank lines.
+///
+/// Example:
+/// ```rust,no_run
+/// use simple_aws_s3::parse_access_log;
+///
+/// # async fn run(s3: simple_aws_s3::S3) -> Result<(), simple_aws_s3::error::Error> {
+/// let log = s3.get_object_text("logs/2019-02-06-00-00-38-5A2F4CEXAMPLE").await?;
+/// for record in parse_access_log(log.as_deref().unwrap_or_default()) {
+///     let record = record?;
+///     if record.http_status == Some(403) {
+///         println!("{:?} denied {}", record.requester, record.operation);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn parse_access_log(log: &str) -> impl Iterator<Item = Result<AccessLogRecord, Error>> + '_ {
+    log.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(AccessLogRecord::parse)
+}
+
+/// Split a record into its fields: separated by spaces, except inside `[...]` and `"..."`,
+/// which are returned without their delimiters. A quoted field ends at an unescaped quote
+/// followed by a space or the end of the line, so quotes inside user agents are kept, escaped
+/// by S3 as `\"` or not.
+#[inline]
+fn split_fields(line: &str) -> Result<Vec<&str>, Error> {
+    let mut fields = vec![];
+    let mut rest = line.trim_start();
+
+    while !rest.is_empty() {
+        let (field, next) = if let Some(inner) = rest.strip_prefix('[') {
+            let end = inner.find(']').ok_or_else(|| unterminated(line))?;
+            (&inner[..end], &inner[end + 1..])
+        } else if let Some(inner) = rest.strip_prefix('"') {
+            let end = inner
+                .match_indices('"')
+                .map(|(i, _)| i)
+                .filter(|i| is_unescaped(inner, *i))
+                .find(|i| inner[i + 1..].is_empty() || inner[i + 1..].starts_with(' '))
+                .ok_or_else(|| unterminated(line))?;
+            (&inner[..end], &inner[end + 1..])
+        } else {
+            let end = rest.find(' ').unwrap_or(rest.len());
+            (&rest[..end], &rest[end..])
+        };
+        fields.push(field);
+        rest = next.trim_start();
+    }
+    Ok(fields)
+}
+
+/// Whether the character at `i` is not escaped by a backslash, itself unescaped.
+#[inline]
+fn is_unescaped(field: &str, i: usize) -> bool {
+    field[..i].chars().rev().take_while(|c| *c == '\\').count() % 2 == 0
+}
+
+#[inline]
+fn unterminated(line: &str) -> Error {
+    Error::InvalidInput(format!(
+        "Unterminated field in access log record: {:?}",
+        line
+    ))
+}
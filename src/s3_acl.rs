@@ -0,0 +1,321 @@
+use std::fmt;
+use std::str::FromStr;
+
+use reqwest::Method;
+
+use crate::error::Error;
+use crate::s3_delete::escape_xml;
+use crate::{RequestOptions, S3};
+
+/// Permission granted by an ACL grant.
+///
+/// Ref: https://docs.aws.amazon.com/AmazonS3/latest/userguide/acl-overview.html#permissions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Permission {
+    FullControl,
+    Write,
+    WriteAcp,
+    Read,
+    ReadAcp,
+}
+
+impl Permission {
+    #[inline]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::FullControl => "FULL_CONTROL",
+            Self::Write => "WRITE",
+            Self::WriteAcp => "WRITE_ACP",
+            Self::Read => "READ",
+            Self::ReadAcp => "READ_ACP",
+        }
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Permission {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "FULL_CONTROL" => Ok(Self::FullControl),
+            "WRITE" => Ok(Self::Write),
+            "WRITE_ACP" => Ok(Self::WriteAcp),
+            "READ" => Ok(Self::Read),
+            "READ_ACP" => Ok(Self::ReadAcp),
+            _ => Err(Error::InvalidInput(format!("Unknown permission {:?}", s))),
+        }
+    }
+}
+
+/// Grantee of an ACL grant.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Grantee {
+    /// An AWS account, by canonical user id
+    CanonicalUser {
+        id: String,
+        display_name: Option<String>,
+    },
+    /// An AWS account, by email address (only in some regions)
+    Email(String),
+    /// A predefined group, by uri, e.g. `http://acs.amazonaws.com/groups/global/AllUsers`
+    Group(String),
+}
+
+impl Grantee {
+    /// Everyone, including anonymous users.
+    pub const ALL_USERS: &'static str = "http://acs.amazonaws.com/groups/global/AllUsers";
+    /// Any authenticated AWS account.
+    pub const AUTHENTICATED_USERS: &'static str =
+        "http://acs.amazonaws.com/groups/global/AuthenticatedUsers";
+    /// The S3 log delivery group, writing server access logs.
+    pub const LOG_DELIVERY: &'static str = "http://acs.amazonaws.com/groups/s3/LogDelivery";
+
+    #[inline]
+    pub fn canonical_user(id: impl Into<String>) -> Self {
+        Self::CanonicalUser {
+            id: id.into(),
+            display_name: None,
+        }
+    }
+
+    #[inline]
+    fn to_xml(&self) -> String {
+        let (kind, inner) = match self {
+            Self::CanonicalUser { id, .. } => {
+                ("CanonicalUser", format!("<ID>{}</ID>", escape_xml(id)))
+            }
+            Self::Email(email) => (
+                "AmazonCustomerByEmail",
+                format!("<EmailAddress>{}</EmailAddress>", escape_xml(email)),
+            ),
+            Self::Group(uri) => ("Group", format!("<URI>{}</URI>", escape_xml(uri))),
+        };
+        format!(
+            r#"<Grantee xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:type="{}">{}</Grantee>"#,
+            kind, inner
+        )
+    }
+}
+
+/// A permission granted to a grantee.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grant {
+    pub grantee: Grantee,
+    pub permission: Permission,
+}
+
+impl Grant {
+    #[inline]
+    pub fn new(grantee: Grantee, permission: Permission) -> Self {
+        Self {
+            grantee,
+            permission,
+        }
+    }
+}
+
+/// Owner of a bucket or an object.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Owner {
+    #[serde(rename = "ID")]
+    pub id: String,
+    #[serde(rename = "DisplayName", default)]
+    pub display_name: Option<String>,
+}
+
+/// Access control list of a bucket, with its owner.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::{AccessControlPolicy, Grant, Grantee, Permission};
+///
+/// let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <AccessControlPolicy xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+///   <Owner><ID>75aa57f09aa0c8caeab4f8c24e99d10f8e7faeebf76c078efc7c6caea54ba06a</ID></Owner>
+///   <AccessControlList>
+///     <Grant>
+///       <Grantee xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:type="CanonicalUser">
+///         <ID>75aa57f09aa0c8caeab4f8c24e99d10f8e7faeebf76c078efc7c6caea54ba06a</ID>
+///         <DisplayName>mtd@amazon.com</DisplayName>
+///       </Grantee>
+///       <Permission>FULL_CONTROL</Permission>
+///     </Grant>
+///   </AccessControlList>
+/// </AccessControlPolicy>"#;
+///
+/// let mut policy = AccessControlPolicy::from_xml(body).unwrap();
+/// assert_eq!(policy.grants[0].permission, Permission::FullControl);
+///
+/// // Let everyone read the bucket listing
+/// policy.grants.push(Grant::new(Grantee::Group(Grantee::ALL_USERS.into()), Permission::Read));
+/// assert!(policy.to_xml().contains("<URI>http://acs.amazonaws.com/groups/global/AllUsers</URI></Grantee><Permission>READ</Permission>"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessControlPolicy {
+    pub owner: Owner,
+    pub grants: Vec<Grant>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct AccessControlPolicyXml {
+    owner: Owner,
+    access_control_list: AccessControlListXml,
+}
+
+#[derive(Debug, Deserialize)]
+struct AccessControlListXml {
+    #[serde(rename = "Grant", default)]
+    grants: Vec<GrantXml>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GrantXml {
+    grantee: GranteeXml,
+    permission: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GranteeXml {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(rename = "ID", default)]
+    id: Option<String>,
+    #[serde(rename = "DisplayName", default)]
+    display_name: Option<String>,
+    #[serde(rename = "EmailAddress", default)]
+    email_address: Option<String>,
+    #[serde(rename = "URI", default)]
+    uri: Option<String>,
+}
+
+impl GranteeXml {
+    #[inline]
+    fn into_grantee(self) -> Result<Grantee, Error> {
+        let missing =
+            |field: &str| Error::InvalidInput(format!("{} grantee without {}", self.kind, field));
+        match self.kind.as_str() {
+            "CanonicalUser" => Ok(Grantee::CanonicalUser {
+                id: self.id.clone().ok_or_else(|| missing("ID"))?,
+                display_name: self.display_name.clone(),
+            }),
+            "AmazonCustomerByEmail" => Ok(Grantee::Email(
+                self.email_address
+                    .clone()
+                    .ok_or_else(|| missing("EmailAddress"))?,
+            )),
+            "Group" => Ok(Grantee::Group(
+                self.uri.clone().ok_or_else(|| missing("URI"))?,
+            )),
+            kind => Err(Error::InvalidInput(format!(
+                "Unknown grantee type {:?}",
+                kind
+            ))),
+        }
+    }
+}
+
+impl AccessControlPolicy {
+    #[inline]
+    pub fn from_xml(body: &str) -> Result<Self, Error> {
+        let policy: AccessControlPolicyXml = serde_xml_rs::from_str(body)?;
+        let grants = policy
+            .access_control_list
+            .grants
+            .into_iter()
+            .map(|grant| {
+                Ok(Grant {
+                    grantee: grant.grantee.into_grantee()?,
+                    permission: grant.permission.parse()?,
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(Self {
+            owner: policy.owner,
+            grants,
+        })
+    }
+
+    #[inline]
+    pub fn to_xml(&self) -> String {
+        let mut body = String::from(
+            r#"<AccessControlPolicy xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Owner>"#,
+        );
+        body.push_str(&format!("<ID>{}</ID>", escape_xml(&self.owner.id)));
+        if let Some(display_name) = &self.owner.display_name {
+            body.push_str(&format!(
+                "<DisplayName>{}</DisplayName>",
+                escape_xml(display_name)
+            ));
+        }
+        body.push_str("</Owner><AccessControlList>");
+        for grant in &self.grants {
+            body.push_str("<Grant>");
+            body.push_str(&grant.grantee.to_xml());
+            body.push_str(&format!(
+                "<Permission>{}</Permission></Grant>",
+                grant.permission
+            ));
+        }
+        body.push_str("</AccessControlList></AccessControlPolicy>");
+        body
+    }
+}
+
+impl S3 {
+    /// Get the access control list of the bucket.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::{Grantee, S3};
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let policy = s3.get_bucket_acl().await?;
+    /// let public = policy
+    ///     .grants
+    ///     .iter()
+    ///     .any(|grant| grant.grantee == Grantee::Group(Grantee::ALL_USERS.into()));
+    /// println!("public: {}", public);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn get_bucket_acl(&self) -> Result<AccessControlPolicy, Error> {
+        let req = self.prepare_request(
+            Method::GET,
+            None,
+            &[("acl", "")],
+            &RequestOptions::default(),
+        )?;
+        let res = self.execute(req).await?;
+        AccessControlPolicy::from_xml(&res.text().await?)
+    }
+
+    /// Replace the access control list of the bucket.
+    #[inline]
+    pub async fn put_bucket_acl(&self, policy: &AccessControlPolicy) -> Result<(), Error> {
+        let req = self.prepare_xml_request(Method::PUT, None, &[("acl", "")], policy.to_xml())?;
+        self.execute(req).await?;
+        Ok(())
+    }
+
+    /// Replace the access control list of the bucket by a canned ACL, e.g. `private`.
+    #[inline]
+    pub async fn put_bucket_canned_acl(&self, canned_acl: &str) -> Result<(), Error> {
+        let req = self.prepare_request(
+            Method::PUT,
+            None,
+            &[("acl", "")],
+            &RequestOptions::new().acl(canned_acl),
+        )?;
+        self.execute(req).await?;
+        Ok(())
+    }
+}
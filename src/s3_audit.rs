@@ -0,0 +1,186 @@
+use futures_util::stream::{self, StreamExt};
+use reqwest::header::{CONTENT_LENGTH, ETAG};
+
+use crate::error::Error;
+use crate::s3_constant::*;
+use crate::{ListObjectsOptions, ObjectSummary, RequestOptions, S3Key, S3};
+
+/// Expectations checked by `audit_prefix`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditOptions {
+    /// Objects must be at least this many bytes
+    pub min_size: Option<u64>,
+    /// Objects must be at most this many bytes
+    pub max_size: Option<u64>,
+    /// Objects must be stored in this class, e.g. `STANDARD` or `GLACIER`
+    pub storage_class: Option<String>,
+    /// Objects must have an additional checksum (`x-amz-checksum-*`)
+    pub require_checksum: bool,
+    /// Number of objects headed concurrently
+    pub concurrency: usize,
+}
+
+impl Default for AuditOptions {
+    fn default() -> Self {
+        Self {
+            min_size: None,
+            max_size: None,
+            storage_class: None,
+            require_checksum: false,
+            concurrency: 8,
+        }
+    }
+}
+
+/// Result of `audit_prefix`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditReport {
+    /// Number of objects checked
+    pub checked: usize,
+    pub issues: Vec<AuditIssue>,
+}
+
+impl AuditReport {
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditIssue {
+    pub key: String,
+    pub problem: AuditProblem,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditProblem {
+    /// The object was listed but is gone
+    Missing,
+    /// The object could not be headed
+    RequestFailed(String),
+    /// The size of the object is outside of the expected range
+    SizeOutOfRange(u64),
+    /// The size returned by the head differs from the listing
+    SizeMismatch {
+        listed: u64,
+        actual: u64,
+    },
+    /// The ETag returned by the head differs from the listing
+    ETagMismatch {
+        listed: String,
+        actual: String,
+    },
+    StorageClassMismatch {
+        expected: String,
+        actual: String,
+    },
+    MissingChecksum,
+}
+
+impl S3 {
+    /// List the objects under `prefix`, head them and check them against `options`.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::{AuditOptions, S3};
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let options = AuditOptions {
+    ///     min_size: Some(1),
+    ///     storage_class: Some("GLACIER".into()),
+    ///     ..Default::default()
+    /// };
+    /// let report = s3.audit_prefix("backups/", &options).await?;
+    /// for issue in report.issues {
+    ///     eprintln!("{}: {:?}", issue.key, issue.problem);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn audit_prefix(
+        &self,
+        prefix: &str,
+        options: &AuditOptions,
+    ) -> Result<AuditReport, Error> {
+        let objects = self
+            .list_all_objects_v2(&ListObjectsOptions::new().prefix(prefix))
+            .await?;
+        let checked = objects.len();
+
+        let issues = stream::iter(objects)
+            .map(|object| self.audit_object(object, options))
+            .buffer_unordered(options.concurrency.max(1))
+            .collect::<Vec<_>>()
+            .await;
+        let mut issues = issues.into_iter().flatten().collect::<Vec<_>>();
+        issues.sort_by(|a, b| a.key.cmp(&b.key));
+
+        Ok(AuditReport { checked, issues })
+    }
+
+    #[inline]
+    async fn audit_object(&self, object: ObjectSummary, options: &AuditOptions) -> Vec<AuditIssue> {
+        let mut problems = vec![];
+
+        if matches!(options.min_size, Some(min) if object.size < min)
+            || matches!(options.max_size, Some(max) if object.size > max)
+        {
+            problems.push(AuditProblem::SizeOutOfRange(object.size));
+        }
+
+        let request_options = RequestOptions::new().checksum_mode(options.require_checksum);
+        let head = match S3Key::lenient(object.key.as_str()) {
+            Ok(key) => self.head_object_with_options(key, &request_options).await,
+            Err(e) => Err(e),
+        };
+        match head {
+            Ok(Some(res)) => {
+                let headers = res.headers();
+                let header = |name| headers.get(name).and_then(|v| v.to_str().ok());
+
+                let size = header(CONTENT_LENGTH.as_str()).and_then(|v| v.parse().ok());
+                if let Some(size) = size.filter(|size| *size != object.size) {
+                    problems.push(AuditProblem::SizeMismatch {
+                        listed: object.size,
+                        actual: size,
+                    });
+                }
+                if let Some(etag) = header(ETAG.as_str()).filter(|etag| *etag != object.etag) {
+                    problems.push(AuditProblem::ETagMismatch {
+                        listed: object.etag.clone(),
+                        actual: etag.to_string(),
+                    });
+                }
+                if let Some(expected) = &options.storage_class {
+                    // S3 omits the header for STANDARD objects
+                    let actual = header(S3_STORAGE_CLASS_KEY).unwrap_or("STANDARD");
+                    if actual != expected {
+                        problems.push(AuditProblem::StorageClassMismatch {
+                            expected: expected.clone(),
+                            actual: actual.to_string(),
+                        });
+                    }
+                }
+                if options.require_checksum
+                    && !S3_CHECKSUM_KEYS
+                        .iter()
+                        .any(|name| headers.contains_key(*name))
+                {
+                    problems.push(AuditProblem::MissingChecksum);
+                }
+            }
+            Ok(None) => problems.push(AuditProblem::Missing),
+            Err(e) => problems.push(AuditProblem::RequestFailed(e.to_string())),
+        }
+
+        problems
+            .into_iter()
+            .map(|problem| AuditIssue {
+                key: object.key.clone(),
+                problem,
+            })
+            .collect()
+    }
+}
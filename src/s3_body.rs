@@ -0,0 +1,139 @@
+use std::convert::TryInto;
+
+use bytes::{Bytes, BytesMut};
+use reqwest::Response;
+
+use crate::error::Error;
+use crate::{S3Key, S3};
+
+/// Read the body of `res`, failing with `Error::BodyTooLarge` as soon as it exceeds `max` bytes
+/// instead of buffering it all.
+///
+/// The declared `Content-Length` is checked first, so oversized bodies are usually rejected
+/// before any byte is downloaded.
+#[inline]
+pub async fn bytes_limited(mut res: Response, max: u64) -> Result<Bytes, Error> {
+    if let Some(length) = res.content_length().filter(|length| *length > max) {
+        return Err(Error::BodyTooLarge {
+            limit: max,
+            length: Some(length),
+        });
+    }
+
+    let mut body = BytesMut::new();
+    while let Some(chunk) = res.chunk().await? {
+        if body.len() as u64 + chunk.len() as u64 > max {
+            return Err(Error::BodyTooLarge {
+                limit: max,
+                length: None,
+            });
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body.freeze())
+}
+
+/// Read the body of `res` as text like `Response::text`, decoded with the charset of its content
+/// type, failing with `Error::BodyTooLarge` once it exceeds `max` bytes.
+#[inline]
+pub(crate) async fn text_limited(res: Response, max: u64) -> Result<String, Error> {
+    let headers = res.headers().clone();
+    let mut text = http::Response::new(bytes_limited(res, max).await?);
+    *text.headers_mut() = headers;
+    Ok(Response::from(text).text().await?)
+}
+
+impl S3 {
+    /// Get the content of an object, `None` if it does not exist.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::S3;
+    /// use simple_aws_s3::error::Error;
+    ///
+    /// # async fn run(s3: S3) -> Result<(), Error> {
+    /// match s3.get_object_bytes("uploads/avatar.png").await? {
+    ///     Some(bytes) => println!("{} bytes", bytes.len()),
+    ///     None => println!("not found"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn get_object_bytes(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+    ) -> Result<Option<Bytes>, Error> {
+        match self.get_object(key).await? {
+            Some(output) => Ok(Some(output.bytes().await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the content of an object as text, decoded with the charset of its content type
+    /// (UTF-8 by default), `None` if it does not exist.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::S3;
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// if let Some(readme) = s3.get_object_text("docs/README.md").await? {
+    ///     println!("{}", readme);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn get_object_text(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+    ) -> Result<Option<String>, Error> {
+        match self.get_object(key).await? {
+            Some(output) => Ok(Some(output.text().await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the content of an object of at most `max` bytes, `None` if it does not exist.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::S3;
+    /// use simple_aws_s3::error::Error;
+    ///
+    /// # async fn run(s3: S3) -> Result<(), Error> {
+    /// match s3.get_object_bytes_limited("uploads/avatar.png", 10 * 1024 * 1024).await {
+    ///     Ok(Some(bytes)) => println!("{} bytes", bytes.len()),
+    ///     Ok(None) => println!("not found"),
+    ///     Err(Error::BodyTooLarge { limit, .. }) => println!("larger than {} bytes", limit),
+    ///     Err(e) => return Err(e),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn get_object_bytes_limited(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        max: u64,
+    ) -> Result<Option<Bytes>, Error> {
+        match self.get_object(key).await? {
+            Some(output) => Ok(Some(bytes_limited(output.into_response(), max).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the content of an object of at most `max` bytes as text, like `get_object_text`.
+    #[inline]
+    pub async fn get_object_text_limited(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        max: u64,
+    ) -> Result<Option<String>, Error> {
+        match self.get_object(key).await? {
+            Some(output) => Ok(Some(text_limited(output.into_response(), max).await?)),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use reqwest::Method;
+
+use crate::error::Error;
+use crate::s3_delete::escape_xml;
+use crate::{
+    BucketEncryption, CorsRule, LifecycleRule, PublicAccessBlock, RequestOptions, VersioningStatus,
+    S3,
+};
+
+/// Declared configuration of a bucket, converged by `S3::ensure_bucket`. Settings left to
+/// `None` are not managed: whatever the bucket has is kept.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::{BucketConfig, BucketEncryption, CorsRule, LifecycleRule, PublicAccessBlock, VersioningStatus};
+///
+/// let config = BucketConfig::new()
+///     .versioning(VersioningStatus::Enabled)
+///     .encryption(BucketEncryption::s3_managed())
+///     .public_access_block(PublicAccessBlock::all())
+///     .cors(vec![CorsRule::new("https://app.example.com", &["POST"]).allowed_header("*")])
+///     .lifecycle(vec![LifecycleRule::new("expire-tmp", "tmp/").expiration_days(1)]);
+/// assert!(config.cors.is_some());
+/// assert!(BucketConfig::new().lifecycle.is_none());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BucketConfig {
+    /// `Suspended` is also satisfied by a bucket never versioned
+    pub versioning: Option<VersioningStatus>,
+    pub encryption: Option<BucketEncryption>,
+    pub public_access_block: Option<PublicAccessBlock>,
+    /// CORS rules, an empty list removes the rules of the bucket
+    pub cors: Option<Vec<CorsRule>>,
+    /// Lifecycle rules, an empty list removes the rules of the bucket
+    pub lifecycle: Option<Vec<LifecycleRule>>,
+}
+
+impl BucketConfig {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn versioning(mut self, status: VersioningStatus) -> Self {
+        self.versioning = Some(status);
+        self
+    }
+
+    #[inline]
+    pub fn encryption(mut self, encryption: BucketEncryption) -> Self {
+        self.encryption = Some(encryption);
+        self
+    }
+
+    #[inline]
+    pub fn public_access_block(mut self, block: PublicAccessBlock) -> Self {
+        self.public_access_block = Some(block);
+        self
+    }
+
+    #[inline]
+    pub fn cors(mut self, rules: Vec<CorsRule>) -> Self {
+        self.cors = Some(rules);
+        self
+    }
+
+    #[inline]
+    pub fn lifecycle(mut self, rules: Vec<LifecycleRule>) -> Self {
+        self.lifecycle = Some(rules);
+        self
+    }
+}
+
+/// Change applied to a bucket by `S3::ensure_bucket`, with the previous setting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BucketChange {
+    Created,
+    Versioning {
+        from: Option<VersioningStatus>,
+        to: VersioningStatus,
+    },
+    Encryption {
+        from: Option<BucketEncryption>,
+        to: BucketEncryption,
+    },
+    PublicAccessBlock {
+        from: Option<PublicAccessBlock>,
+        to: PublicAccessBlock,
+    },
+    Cors {
+        from: Vec<CorsRule>,
+        to: Vec<CorsRule>,
+    },
+    Lifecycle {
+        from: Vec<LifecycleRule>,
+        to: Vec<LifecycleRule>,
+    },
+}
+
+/// Result of `ensure_bucket`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BucketSetupReport {
+    /// Changes applied, in order
+    pub changes: Vec<BucketChange>,
+}
+
+impl BucketSetupReport {
+    /// Whether the bucket already matched its configuration.
+    #[inline]
+    pub fn is_unchanged(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    #[inline]
+    pub fn created(&self) -> bool {
+        self.changes.contains(&BucketChange::Created)
+    }
+}
+
+impl S3 {
+    /// Whether the bucket exists. A bucket of another account is `Error::AccessDenied`.
+    #[inline]
+    pub async fn bucket_exists(&self) -> Result<bool, Error> {
+        let req = self.prepare_request(Method::HEAD, None, &[], &RequestOptions::default())?;
+        match self.execute(req).await {
+            Ok(_) => Ok(true),
+            Err(Error::NotFound(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Create the bucket in the region of the client.
+    #[inline]
+    pub async fn create_bucket(&self) -> Result<(), Error> {
+        // Buckets of us-east-1 are created without location constraint
+        let body = Some(self.region())
+            .filter(|region| *region != "us-east-1")
+            .map(|region| {
+                format!(
+                    r#"<CreateBucketConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><LocationConstraint>{}</LocationConstraint></CreateBucketConfiguration>"#,
+                    escape_xml(region)
+                )
+                .into_bytes()
+            });
+        let req = self.prepare_request_with_body(
+            Method::PUT,
+            None,
+            &[],
+            &RequestOptions::default(),
+            &[],
+            body,
+        )?;
+        self.execute(req).await?;
+        Ok(())
+    }
+
+    /// Create the bucket if it does not exist, and converge its settings to `config`. Running
+    /// it again with the same configuration changes nothing.
+    ///
+    /// Settings are only written when they differ, and the report lists every change with the
+    /// previous setting. With `S3::with_dry_run`, the report is the plan and nothing is written.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::{BucketConfig, PublicAccessBlock, VersioningStatus, S3};
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let config = BucketConfig::new()
+    ///     .versioning(VersioningStatus::Enabled)
+    ///     .public_access_block(PublicAccessBlock::all());
+    /// let report = s3.ensure_bucket(&config).await?;
+    /// for change in &report.changes {
+    ///     println!("{:?}", change);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn ensure_bucket(&self, config: &BucketConfig) -> Result<BucketSetupReport, Error> {
+        let mut report = BucketSetupReport::default();
+        let created = if self.bucket_exists().await? {
+            false
+        } else {
+            match self.create_bucket().await {
+                Ok(()) => report.changes.push(BucketChange::Created),
+                // Created meanwhile by another run
+                Err(Error::S3(e)) if e.code == "BucketAlreadyOwnedByYou" => {}
+                Err(e) => return Err(e),
+            }
+            report.created()
+        };
+
+        if let Some(to) = config.versioning {
+            let from = if created {
+                None
+            } else {
+                self.get_bucket_versioning().await?
+            };
+            let converged =
+                from == Some(to) || (from.is_none() && to == VersioningStatus::Suspended);
+            if !converged {
+                self.put_bucket_versioning(to).await?;
+                report.changes.push(BucketChange::Versioning { from, to });
+            }
+        }
+
+        if let Some(to) = &config.encryption {
+            let from = if created {
+                Some(BucketEncryption::s3_managed())
+            } else {
+                self.get_bucket_encryption().await?
+            };
+            if from.as_ref() != Some(to) {
+                self.put_bucket_encryption(to).await?;
+                report.changes.push(BucketChange::Encryption {
+                    from,
+                    to: to.clone(),
+                });
+            }
+        }
+
+        if let Some(to) = config.public_access_block {
+            let from = if created {
+                Some(PublicAccessBlock::all())
+            } else {
+                self.get_public_access_block().await?
+            };
+            if from != Some(to) {
+                self.put_public_access_block(&to).await?;
+                report
+                    .changes
+                    .push(BucketChange::PublicAccessBlock { from, to });
+            }
+        }
+
+        if let Some(to) = &config.cors {
+            let from = if created {
+                vec![]
+            } else {
+                self.get_bucket_cors().await?
+            };
+            if &from != to {
+                if to.is_empty() {
+                    self.delete_bucket_cors().await?;
+                } else {
+                    self.put_bucket_cors(to).await?;
+                }
+                report.changes.push(BucketChange::Cors {
+                    from,
+                    to: to.clone(),
+                });
+            }
+        }
+
+        if let Some(to) = &config.lifecycle {
+            let from = if created {
+                vec![]
+            } else {
+                self.get_bucket_lifecycle().await?
+            };
+            // Rules with elements not modeled (tag filters...) never equal the declared ones
+            if &from != to {
+                if to.is_empty() {
+                    self.delete_bucket_lifecycle().await?;
+                } else {
+                    self.put_bucket_lifecycle(to).await?;
+                }
+                report.changes.push(BucketChange::Lifecycle {
+                    from,
+                    to: to.clone(),
+                });
+            }
+        }
+
+        Ok(report)
+    }
+}
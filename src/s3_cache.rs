@@ -0,0 +1,155 @@
+use std::convert::TryInto;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use bytes::Bytes;
+use reqwest::StatusCode;
+use sha2::{Digest, Sha256};
+use tokio::fs;
+
+use crate::error::Error;
+use crate::s3_key::try_key;
+use crate::{RequestOptions, S3Key, S3};
+
+/// Number of cache entries written by this process, making their temporary files unique.
+static CACHE_WRITE_COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// Read-through disk cache over the objects of an `S3` client.
+///
+/// Downloaded objects are stored in `dir`, each in one file with its ETag. Later gets send the
+/// ETag as `If-None-Match` and are served from disk when S3 answers `304 Not Modified`, so
+/// unchanged objects are only downloaded once.
+///
+/// Example:
+/// ```rust,no_run
+/// use simple_aws_s3::{CachedS3, S3};
+///
+/// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+/// let cached = CachedS3::new(s3, "/var/cache/assets");
+/// let logo = cached.get_object("assets/logo.png").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CachedS3 {
+    s3: S3,
+    dir: PathBuf,
+}
+
+impl CachedS3 {
+    #[inline]
+    pub fn new(s3: S3, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            s3,
+            dir: dir.into(),
+        }
+    }
+
+    #[inline]
+    pub fn s3(&self) -> &S3 {
+        &self.s3
+    }
+
+    #[inline]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Get the content of an object, `None` if it does not exist.
+    #[inline]
+    pub async fn get_object(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+    ) -> Result<Option<Bytes>, Error> {
+        let key = try_key(key)?;
+        let path = self.path(&key);
+
+        // Read at once, so the data served on `304 Not Modified` is the data of the sent ETag
+        let cached = read_entry(&path).await;
+        let mut options = RequestOptions::new();
+        if let Some((etag, _)) = &cached {
+            options = options.if_none_match(etag.as_str());
+        }
+
+        let res = match self.s3.get_object_with_options(&key, &options).await {
+            Ok(Some(res)) => res,
+            Ok(None) => {
+                self.invalidate(&key).await?;
+                return Ok(None);
+            }
+            Err(Error::S3(e)) if e.status == StatusCode::NOT_MODIFIED => match cached {
+                Some((_, data)) => return Ok(Some(data)),
+                None => match self.s3.get_object(&key).await? {
+                    Some(res) => res,
+                    None => return Ok(None),
+                },
+            },
+            Err(e) => return Err(e),
+        };
+
+        let etag = res.etag.clone();
+        let data = res.bytes().await?;
+        if let Some(etag) = etag {
+            self.store(&path, &data, &etag).await?;
+        }
+
+        Ok(Some(data))
+    }
+
+    /// Remove the cached copy of an object.
+    #[inline]
+    pub async fn invalidate(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+    ) -> Result<(), Error> {
+        let key = try_key(key)?;
+        match fs::remove_file(self.path(&key)).await {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    #[inline]
+    async fn store(&self, path: &Path, data: &[u8], etag: &str) -> Result<(), Error> {
+        fs::create_dir_all(&self.dir).await?;
+
+        // Written to a temporary file of this write only, then renamed at once, so readers
+        // never see partial data, nor the data of a concurrent write with this ETag
+        let tmp_path = path.with_extension(format!(
+            "{}-{}.tmp",
+            process::id(),
+            CACHE_WRITE_COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let mut entry = Vec::with_capacity(etag.len() + 1 + data.len());
+        entry.extend_from_slice(etag.as_bytes());
+        entry.push(b'\n');
+        entry.extend_from_slice(data);
+        let res = match fs::write(&tmp_path, entry).await {
+            Ok(()) => fs::rename(&tmp_path, path).await,
+            Err(e) => Err(e),
+        };
+        if res.is_err() {
+            let _ = fs::remove_file(&tmp_path).await;
+        }
+        Ok(res?)
+    }
+
+    /// Path of the cached ETag and data of a key.
+    #[inline]
+    fn path(&self, key: &S3Key) -> PathBuf {
+        let name = hex::encode(Sha256::digest(key.as_bytes()));
+        self.dir.join(format!("{}.cache", name))
+    }
+}
+
+/// ETag and data of a cache entry, stored as the ETag, a line break and the data. `None` if
+/// the entry is missing or malformed.
+#[inline]
+async fn read_entry(path: &Path) -> Option<(String, Bytes)> {
+    let entry = Bytes::from(fs::read(path).await.ok()?);
+    let end = entry.iter().position(|b| *b == b'\n')?;
+    let etag = std::str::from_utf8(&entry[..end]).ok()?.to_string();
+    Some((etag, entry.slice(end + 1..)))
+}
@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use bytes::Bytes;
+use reqwest::StatusCode;
+use sha2::{Digest, Sha256};
+
+use crate::error::Error;
+use crate::s3_constant::{S3_CHECKSUM_SHA256_KEY, S3_SDK_CHECKSUM_ALGORITHM_KEY};
+use crate::{RequestOptions, S3Key, S3};
+
+/// Prefix of the objects of the content-addressed storage.
+pub const CAS_PREFIX: &str = "sha256/";
+
+/// Attempts of a store conflicting with a concurrent store of the same content.
+const CONFLICT_ATTEMPTS: u32 = 5;
+
+/// Key of the content of hex SHA256 `digest` in the content-addressed storage.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::cas_key;
+///
+/// let digest = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+/// assert_eq!(cas_key(digest).unwrap(), format!("sha256/{}", digest));
+/// assert!(cas_key("2CF24DBA").is_err());
+/// assert!(cas_key("../../etc/passwd").is_err());
+/// ```
+#[inline]
+pub fn cas_key(digest: &str) -> Result<String, Error> {
+    let is_digest = digest.len() == 64
+        && digest
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+    if !is_digest {
+        return Err(Error::InvalidInput(format!(
+            "{} is not a lowercase hex SHA256",
+            digest
+        )));
+    }
+    Ok(format!("{}{}", CAS_PREFIX, digest))
+}
+
+impl S3 {
+    /// Store `body` under the hex SHA256 of its content, and return this digest.
+    ///
+    /// Stored contents are immutable: the upload is sent with `If-None-Match: *`, so a content
+    /// already stored is never written again, and concurrent stores of a content are safe: a
+    /// store conflicting with another one in progress (`409 Conflict`) is sent again, until S3
+    /// reports the content as stored. S3 verifies the content against its checksum.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::S3;
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let digest = s3.cas_put(b"hello".to_vec()).await?;
+    /// assert_eq!(digest, "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824");
+    /// assert!(s3.cas_exists(&digest).await?);
+    /// assert_eq!(s3.cas_get(&digest).await?.unwrap(), "hello");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn cas_put(&self, body: Vec<u8>) -> Result<String, Error> {
+        let sha256 = Sha256::digest(&body);
+        let digest = hex::encode(sha256);
+        let checksum = base64::encode(sha256);
+        let key = S3Key::new(cas_key(&digest)?)?;
+
+        let options = RequestOptions::new()
+            .if_none_match("*")
+            .header(S3_SDK_CHECKSUM_ALGORITHM_KEY, "SHA256")
+            .header(S3_CHECKSUM_SHA256_KEY, checksum);
+        let mut attempt = 1;
+        loop {
+            match self
+                .put_object_with_options(&key, body.clone(), &options)
+                .await
+            {
+                Ok(_) => return Ok(digest),
+                // Already stored
+                Err(Error::S3(e)) if e.status == StatusCode::PRECONDITION_FAILED => {
+                    return Ok(digest)
+                }
+                // Stored concurrently, the other store is still in progress
+                Err(Error::S3(e))
+                    if e.status == StatusCode::CONFLICT && attempt < CONFLICT_ATTEMPTS =>
+                {
+                    tokio::time::sleep(Duration::from_millis(100 * u64::from(attempt))).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Get the content of hex SHA256 `digest`, `None` if it is not stored. The content is
+    /// verified, a corrupted content is `Error::ChecksumMismatch`.
+    pub async fn cas_get(&self, digest: &str) -> Result<Option<Bytes>, Error> {
+        let key = cas_key(digest)?;
+        let body = match self.get_object_bytes(key).await? {
+            Some(body) => body,
+            None => return Ok(None),
+        };
+        let actual = hex::encode(Sha256::digest(&body));
+        if actual != digest {
+            return Err(Error::ChecksumMismatch {
+                expected: digest.to_string(),
+                actual,
+            });
+        }
+        Ok(Some(body))
+    }
+
+    /// Whether the content of hex SHA256 `digest` is stored.
+    #[inline]
+    pub async fn cas_exists(&self, digest: &str) -> Result<bool, Error> {
+        Ok(self.head_object(cas_key(digest)?).await?.is_some())
+    }
+}
@@ -0,0 +1,45 @@
+use std::net::{IpAddr, SocketAddr};
+
+use reqwest::{Certificate, Client, Identity, Proxy};
+
+use crate::error::Error;
+
+/// Settings of the http client of an `S3`, kept to rebuild the client when one changes.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ClientConfig {
+    pub(crate) proxies: Vec<Proxy>,
+    /// Trusted in addition to the root certificates of the system
+    pub(crate) root_certificates: Vec<Certificate>,
+    pub(crate) accept_invalid_certs: bool,
+    /// Client certificate for mutual TLS
+    pub(crate) identity: Option<Identity>,
+    /// Hosts resolved to a fixed ip instead of through DNS
+    pub(crate) resolve_overrides: Vec<(String, IpAddr)>,
+}
+
+impl ClientConfig {
+    #[inline]
+    pub(crate) fn build(&self) -> Result<Client, Error> {
+        let mut builder = Client::builder();
+        for proxy in &self.proxies {
+            builder = builder.proxy(proxy.clone());
+        }
+        for certificate in &self.root_certificates {
+            builder = builder.add_root_certificate(certificate.clone());
+        }
+        if let Some(identity) = &self.identity {
+            builder = builder.identity(identity.clone());
+        }
+        for (host, ip) in &self.resolve_overrides {
+            // The port is ignored, the one of the url is used
+            builder = builder.resolve(host, SocketAddr::new(*ip, 0));
+        }
+        if self.accept_invalid_certs {
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+
+        builder
+            .build()
+            .map_err(|e| Error::ConfigError(format!("Invalid http client configuration: {}", e)))
+    }
+}
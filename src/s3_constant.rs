@@ -6,3 +6,45 @@ pub const S3_EXPIRES_KEY: &str = "X-Amz-Expires";
 pub const S3_SIGNED_HEADERS_KEY: &str = "X-Amz-SignedHeaders";
 pub const S3_CONTENT_KEY: &str = "X-Amz-Content-Sha256";
 pub const S3_ALGO_VALUE: &str = "AWS4-HMAC-SHA256";
+/// Payload hash of requests whose body is not signed, e.g. streamed bodies
+pub const S3_UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+pub const S3_EXPECTED_BUCKET_OWNER_KEY: &str = "x-amz-expected-bucket-owner";
+pub const S3_DELETE_MARKER_KEY: &str = "x-amz-delete-marker";
+pub const S3_VERSION_ID_KEY: &str = "x-amz-version-id";
+pub const S3_CHECKSUM_MODE_KEY: &str = "x-amz-checksum-mode";
+pub const S3_STORAGE_CLASS_KEY: &str = "x-amz-storage-class";
+pub const S3_BUCKET_REGION_KEY: &str = "x-amz-bucket-region";
+pub const S3_EXPIRATION_KEY: &str = "x-amz-expiration";
+pub const S3_REPLICATION_STATUS_KEY: &str = "x-amz-replication-status";
+pub const S3_TAGGING_COUNT_KEY: &str = "x-amz-tagging-count";
+pub const S3_ACL_KEY: &str = "x-amz-acl";
+pub const S3_GRANT_READ_KEY: &str = "x-amz-grant-read";
+pub const S3_GRANT_READ_ACP_KEY: &str = "x-amz-grant-read-acp";
+pub const S3_GRANT_WRITE_ACP_KEY: &str = "x-amz-grant-write-acp";
+pub const S3_GRANT_FULL_CONTROL_KEY: &str = "x-amz-grant-full-control";
+pub const S3_COPY_SOURCE_KEY: &str = "x-amz-copy-source";
+pub const S3_COPY_SOURCE_VERSION_ID_KEY: &str = "x-amz-copy-source-version-id";
+pub const S3_COPY_SOURCE_IF_MATCH_KEY: &str = "x-amz-copy-source-if-match";
+pub const S3_COPY_SOURCE_IF_NONE_MATCH_KEY: &str = "x-amz-copy-source-if-none-match";
+pub const S3_COPY_SOURCE_IF_MODIFIED_SINCE_KEY: &str = "x-amz-copy-source-if-modified-since";
+pub const S3_COPY_SOURCE_IF_UNMODIFIED_SINCE_KEY: &str = "x-amz-copy-source-if-unmodified-since";
+pub const S3_METADATA_DIRECTIVE_KEY: &str = "x-amz-metadata-directive";
+pub const S3_TAGGING_KEY: &str = "x-amz-tagging";
+pub const S3_TAGGING_DIRECTIVE_KEY: &str = "x-amz-tagging-directive";
+pub const S3_OBJECT_LOCK_MODE_KEY: &str = "x-amz-object-lock-mode";
+pub const S3_OBJECT_LOCK_RETAIN_UNTIL_DATE_KEY: &str = "x-amz-object-lock-retain-until-date";
+pub const S3_SSE_KEY: &str = "x-amz-server-side-encryption";
+pub const S3_SSE_KMS_KEY_ID_KEY: &str = "x-amz-server-side-encryption-aws-kms-key-id";
+/// Prefix of the headers carrying user metadata
+pub const S3_METADATA_PREFIX: &str = "x-amz-meta-";
+pub const S3_SDK_CHECKSUM_ALGORITHM_KEY: &str = "x-amz-sdk-checksum-algorithm";
+pub const S3_CHECKSUM_SHA256_KEY: &str = "x-amz-checksum-sha256";
+/// Checksum headers returned by S3, when asked with `x-amz-checksum-mode`
+pub const S3_CHECKSUM_KEYS: &[&str] = &[
+    "x-amz-checksum-crc32",
+    "x-amz-checksum-crc32c",
+    "x-amz-checksum-sha1",
+    "x-amz-checksum-sha256",
+];
+/// Service of the signing scope of S3 Object Lambda access points
+pub const S3_OBJECT_LAMBDA_SERVICE: &str = "s3-object-lambda";
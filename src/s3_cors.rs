@@ -0,0 +1,191 @@
+use reqwest::Method;
+
+use crate::error::Error;
+use crate::s3_delete::escape_xml;
+use crate::{RequestOptions, S3};
+
+/// CORS rule of a bucket, allowing browsers on other origins to send requests to it.
+///
+/// Ref: https://docs.aws.amazon.com/AmazonS3/latest/userguide/cors.html
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::CorsRule;
+///
+/// let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <CORSConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+///   <CORSRule>
+///     <AllowedOrigin>https://www.example.com</AllowedOrigin>
+///     <AllowedMethod>PUT</AllowedMethod>
+///     <AllowedMethod>POST</AllowedMethod>
+///     <AllowedHeader>*</AllowedHeader>
+///     <ExposeHeader>ETag</ExposeHeader>
+///     <MaxAgeSeconds>3000</MaxAgeSeconds>
+///   </CORSRule>
+/// </CORSConfiguration>"#;
+///
+/// let rules = CorsRule::from_xml(body).unwrap();
+/// assert_eq!(
+///     rules,
+///     vec![CorsRule::new("https://www.example.com", &["PUT", "POST"])
+///         .allowed_header("*")
+///         .expose_header("ETag")
+///         .max_age_seconds(3000)]
+/// );
+/// assert!(CorsRule::to_xml(&rules).contains("<AllowedMethod>PUT</AllowedMethod><AllowedMethod>POST</AllowedMethod>"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Deserialize)]
+pub struct CorsRule {
+    #[serde(rename = "ID", default)]
+    pub id: Option<String>,
+    /// Origins allowed, e.g. `https://www.example.com` or `*`
+    #[serde(rename = "AllowedOrigin", default)]
+    pub allowed_origins: Vec<String>,
+    /// `GET`, `PUT`, `POST`, `DELETE` or `HEAD`
+    #[serde(rename = "AllowedMethod", default)]
+    pub allowed_methods: Vec<String>,
+    /// Headers allowed in the preflight requests, e.g. `*`
+    #[serde(rename = "AllowedHeader", default)]
+    pub allowed_headers: Vec<String>,
+    /// Response headers readable by the browser, e.g. `ETag` for multipart uploads
+    #[serde(rename = "ExposeHeader", default)]
+    pub expose_headers: Vec<String>,
+    /// Seconds the browser caches the preflight response
+    #[serde(rename = "MaxAgeSeconds", default)]
+    pub max_age_seconds: Option<u32>,
+}
+
+impl CorsRule {
+    #[inline]
+    pub fn new(origin: impl Into<String>, methods: &[&str]) -> Self {
+        Self {
+            allowed_origins: vec![origin.into()],
+            allowed_methods: methods.iter().map(|method| method.to_string()).collect(),
+            ..Self::default()
+        }
+    }
+
+    #[inline]
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    #[inline]
+    pub fn allowed_origin(mut self, origin: impl Into<String>) -> Self {
+        self.allowed_origins.push(origin.into());
+        self
+    }
+
+    #[inline]
+    pub fn allowed_header(mut self, header: impl Into<String>) -> Self {
+        self.allowed_headers.push(header.into());
+        self
+    }
+
+    #[inline]
+    pub fn expose_header(mut self, header: impl Into<String>) -> Self {
+        self.expose_headers.push(header.into());
+        self
+    }
+
+    #[inline]
+    pub fn max_age_seconds(mut self, seconds: u32) -> Self {
+        self.max_age_seconds = Some(seconds);
+        self
+    }
+
+    /// Parse the rules of a GetBucketCors response.
+    #[inline]
+    pub fn from_xml(body: &str) -> Result<Vec<Self>, Error> {
+        let config: CorsConfigurationXml = serde_xml_rs::from_str(body)?;
+        Ok(config.rules)
+    }
+
+    #[inline]
+    pub fn to_xml(rules: &[Self]) -> String {
+        let mut body =
+            String::from(r#"<CORSConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#);
+        for rule in rules {
+            body.push_str("<CORSRule>");
+            if let Some(id) = &rule.id {
+                body.push_str(&format!("<ID>{}</ID>", escape_xml(id)));
+            }
+            let lists = [
+                ("AllowedOrigin", &rule.allowed_origins),
+                ("AllowedMethod", &rule.allowed_methods),
+                ("AllowedHeader", &rule.allowed_headers),
+                ("ExposeHeader", &rule.expose_headers),
+            ];
+            for (name, values) in lists {
+                for value in values {
+                    body.push_str(&format!("<{0}>{1}</{0}>", name, escape_xml(value)));
+                }
+            }
+            if let Some(seconds) = rule.max_age_seconds {
+                body.push_str(&format!("<MaxAgeSeconds>{}</MaxAgeSeconds>", seconds));
+            }
+            body.push_str("</CORSRule>");
+        }
+        body.push_str("</CORSConfiguration>");
+        body
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CorsConfigurationXml {
+    #[serde(rename = "CORSRule", default)]
+    rules: Vec<CorsRule>,
+}
+
+impl S3 {
+    /// Get the CORS rules of the bucket, empty if it has none.
+    #[inline]
+    pub async fn get_bucket_cors(&self) -> Result<Vec<CorsRule>, Error> {
+        let req = self.prepare_request(
+            Method::GET,
+            None,
+            &[("cors", "")],
+            &RequestOptions::default(),
+        )?;
+        match self.execute(req).await {
+            Ok(res) => CorsRule::from_xml(&res.text().await?),
+            // NoSuchCORSConfiguration
+            Err(Error::NotFound(_)) => Ok(vec![]),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Replace the CORS rules of the bucket, e.g. to allow uploads with presigned posts from a
+    /// web app.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::{CorsRule, S3};
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let rule = CorsRule::new("https://app.example.com", &["POST", "PUT"]).allowed_header("*");
+    /// s3.put_bucket_cors(&[rule]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn put_bucket_cors(&self, rules: &[CorsRule]) -> Result<(), Error> {
+        let req =
+            self.prepare_xml_request(Method::PUT, None, &[("cors", "")], CorsRule::to_xml(rules))?;
+        self.execute(req).await?;
+        Ok(())
+    }
+
+    #[inline]
+    pub async fn delete_bucket_cors(&self) -> Result<(), Error> {
+        let req = self.prepare_request(
+            Method::DELETE,
+            None,
+            &[("cors", "")],
+            &RequestOptions::default(),
+        )?;
+        self.execute(req).await?;
+        Ok(())
+    }
+}
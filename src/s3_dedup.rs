@@ -0,0 +1,124 @@
+use std::convert::TryInto;
+
+use crate::error::Error;
+use crate::s3_constant::{S3_CHECKSUM_SHA256_KEY, S3_SDK_CHECKSUM_ALGORITHM_KEY};
+use crate::s3_delete::checksum_sha256;
+use crate::s3_key::try_key;
+use crate::{HeadObjectOutput, PutObjectOutput, RequestOptions, S3Key, S3};
+
+/// Metadata holding the hex SHA256 of an object, as written by some backup tools.
+pub const SHA256_METADATA: &str = "sha256";
+
+/// Result of `S3::put_object_deduped`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DedupedPut {
+    /// Base64 SHA256 of the content, stored as the `sha256` checksum of the object
+    pub sha256: String,
+    /// The content was uploaded, `false` when the object already had this content
+    pub uploaded: bool,
+    /// Result of the upload, `None` when the object already had this content
+    pub output: Option<PutObjectOutput>,
+}
+
+/// Whether `object` has the content of `size` bytes and base64 SHA256 `sha256`. Objects
+/// uploaded without SHA256 checksum nor metadata never match.
+///
+/// Example:
+/// ```rust
+/// use reqwest::header::{HeaderMap, HeaderValue};
+/// use simple_aws_s3::{has_content, HeadObjectOutput};
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("content-length", HeaderValue::from_static("5"));
+/// headers.insert("x-amz-checksum-sha256", HeaderValue::from_static("LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ="));
+/// let object = HeadObjectOutput::from_headers(headers);
+/// assert!(has_content(&object, 5, "LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ="));
+/// assert!(!has_content(&object, 6, "LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ="));
+///
+/// // Hex SHA256 stored as metadata
+/// let mut headers = HeaderMap::new();
+/// headers.insert("content-length", HeaderValue::from_static("5"));
+/// headers.insert("x-amz-meta-sha256", HeaderValue::from_static("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"));
+/// let object = HeadObjectOutput::from_headers(headers);
+/// assert!(has_content(&object, 5, "LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ="));
+/// ```
+#[inline]
+pub fn has_content(object: &HeadObjectOutput, size: u64, sha256: &str) -> bool {
+    if object.content_length != Some(size) {
+        return false;
+    }
+    let checksum = object.checksums.get("sha256").map(String::as_str);
+    let metadata = object
+        .metadata
+        .get(SHA256_METADATA)
+        .and_then(|hex_sha256| hex::decode(hex_sha256).ok())
+        .map(base64::encode);
+    // Checksums of multipart uploads are checksums of their parts (`<checksum>-<parts>`), so
+    // never equal to the checksum of the content
+    checksum == Some(sha256) || metadata.as_deref() == Some(sha256)
+}
+
+impl S3 {
+    /// Upload `body` as `key`, unless the object already has this content.
+    ///
+    /// The content is compared with its SHA256: the object is uploaded with its checksum, so S3
+    /// verifies the content and stores the checksum, and an upload of the same content again
+    /// only costs a HEAD request. See `has_content`.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::S3;
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let body = std::fs::read("/var/backups/db.dump")?;
+    /// let put = s3.put_object_deduped("backups/db.dump", body).await?;
+    /// if !put.uploaded {
+    ///     println!("backups/db.dump is up to date");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn put_object_deduped(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        body: Vec<u8>,
+    ) -> Result<DedupedPut, Error> {
+        self.put_object_deduped_with_options(key, body, &RequestOptions::default())
+            .await
+    }
+
+    /// Same as `put_object_deduped`, with options such as the content type or metadata of the
+    /// uploaded object.
+    pub async fn put_object_deduped_with_options(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        body: Vec<u8>,
+        options: &RequestOptions,
+    ) -> Result<DedupedPut, Error> {
+        let key = try_key(key)?;
+        let sha256 = checksum_sha256(&body);
+
+        let head = RequestOptions::new().checksum_mode(true);
+        if let Some(object) = self.head_object_with_options(&key, &head).await? {
+            if has_content(&object, body.len() as u64, &sha256) {
+                return Ok(DedupedPut {
+                    sha256,
+                    uploaded: false,
+                    output: None,
+                });
+            }
+        }
+
+        let options = options
+            .clone()
+            .header(S3_SDK_CHECKSUM_ALGORITHM_KEY, "SHA256")
+            .header(S3_CHECKSUM_SHA256_KEY, sha256.as_str());
+        let output = self.put_object_with_options(key, body, &options).await?;
+        Ok(DedupedPut {
+            sha256,
+            uploaded: true,
+            output: Some(output),
+        })
+    }
+}
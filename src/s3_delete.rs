@@ -0,0 +1,194 @@
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use sha2::{Digest, Sha256};
+
+use crate::error::Error;
+use crate::{DeleteObjectsOutput, DeletedObject, ListObjectsOptions, S3};
+
+/// Maximum number of keys of a batch delete request.
+pub const MAX_DELETE_OBJECTS: usize = 1000;
+
+/// Options of a batch delete.
+///
+/// Ref: https://docs.aws.amazon.com/AmazonS3/latest/API/API_DeleteObjects.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeleteObjectsOptions {
+    /// Only failed keys are returned by S3, deleted objects are derived from the requested keys.
+    /// Disable it to get the version and delete marker of each deleted object.
+    pub quiet: bool,
+}
+
+impl Default for DeleteObjectsOptions {
+    #[inline]
+    fn default() -> Self {
+        Self { quiet: true }
+    }
+}
+
+impl DeleteObjectsOptions {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+}
+
+impl S3 {
+    /// Delete several objects, with one request per `MAX_DELETE_OBJECTS` keys.
+    ///
+    /// Keys S3 refuses to delete are returned in `DeleteObjectsOutput::errors` instead of failing
+    /// the whole call.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::S3;
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let output = s3.delete_objects(&["a.txt", "b.txt"]).await?;
+    /// assert!(output.is_ok());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn delete_objects<K: AsRef<str>>(
+        &self,
+        keys: &[K],
+    ) -> Result<DeleteObjectsOutput, Error> {
+        self.delete_objects_with_options(keys, &DeleteObjectsOptions::default())
+            .await
+    }
+
+    /// Delete several objects with options, see `delete_objects`.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::{DeleteObjectsOptions, S3};
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let keys = ["a.txt", "b.txt"];
+    /// let options = DeleteObjectsOptions::new().quiet(false);
+    /// let mut output = s3.delete_objects_with_options(&keys, &options).await?;
+    /// for deleted in &output.deleted {
+    ///     println!("{} deleted, delete marker: {}", deleted.key, deleted.delete_marker);
+    /// }
+    ///
+    /// // Retry only the keys which failed
+    /// if !output.is_ok() {
+    ///     let failed = output.failed_keys().into_iter().map(String::from).collect::<Vec<_>>();
+    ///     output = s3.delete_objects_with_options(&failed, &options).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn delete_objects_with_options<K: AsRef<str>>(
+        &self,
+        keys: &[K],
+        options: &DeleteObjectsOptions,
+    ) -> Result<DeleteObjectsOutput, Error> {
+        let mut output = DeleteObjectsOutput::default();
+        for chunk in keys.chunks(MAX_DELETE_OBJECTS) {
+            let chunk = chunk.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+            output.extend(self.delete_objects_batch(&chunk, options).await?);
+        }
+        Ok(output)
+    }
+
+    /// Delete the objects under `prefix` last modified before `cutoff`.
+    ///
+    /// Useful to expire objects of buckets without lifecycle rules.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use chrono::{Duration, Utc};
+    /// use simple_aws_s3::S3;
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let output = s3.delete_older_than("logs/", Utc::now() - Duration::days(30)).await?;
+    /// println!("expired {} logs", output.deleted.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn delete_older_than(
+        &self,
+        prefix: &str,
+        cutoff: DateTime<Utc>,
+    ) -> Result<DeleteObjectsOutput, Error> {
+        let objects = self
+            .list_all_objects_v2(&ListObjectsOptions::new().prefix(prefix))
+            .await?;
+        let keys = objects
+            .into_iter()
+            .filter(|object| object.last_modified < cutoff)
+            .map(|object| object.key)
+            .collect::<Vec<_>>();
+        self.delete_objects(&keys).await
+    }
+
+    #[inline]
+    async fn delete_objects_batch(
+        &self,
+        keys: &[&str],
+        options: &DeleteObjectsOptions,
+    ) -> Result<DeleteObjectsOutput, Error> {
+        if keys.is_empty() {
+            return Ok(DeleteObjectsOutput::default());
+        }
+
+        let body = delete_request_body(keys, options.quiet);
+        let req = self.prepare_xml_request(Method::POST, None, &[("delete", "")], body)?;
+        let res = self.execute(req).await?;
+        let mut output = DeleteObjectsOutput::from_xml(&res.text().await?)?;
+
+        if options.quiet {
+            output.deleted = keys
+                .iter()
+                .filter(|key| !output.errors.iter().any(|e| e.key == **key))
+                .map(|key| DeletedObject::new(*key))
+                .collect();
+        }
+        Ok(output)
+    }
+}
+
+/// Xml body of a batch delete, in quiet mode only failed keys are returned.
+#[inline]
+fn delete_request_body(keys: &[&str], quiet: bool) -> String {
+    let mut body = format!("<Delete><Quiet>{}</Quiet>", quiet);
+    for key in keys {
+        body.push_str("<Object><Key>");
+        body.push_str(&escape_xml(key));
+        body.push_str("</Key></Object>");
+    }
+    body.push_str("</Delete>");
+    body
+}
+
+/// Base64 SHA256 of a body, sent as `x-amz-checksum-sha256` by the requests which need an
+/// integrity check.
+#[inline]
+pub(crate) fn checksum_sha256(body: &[u8]) -> String {
+    base64::encode(Sha256::digest(body))
+}
+
+#[inline]
+pub(crate) fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
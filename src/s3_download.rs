@@ -0,0 +1,252 @@
+use std::convert::TryInto;
+use std::ffi::OsString;
+use std::io::{self, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use reqwest::header::{CONTENT_LENGTH, ETAG};
+use reqwest::Method;
+use tokio::fs::{self, OpenOptions};
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+
+use crate::error::Error;
+use crate::s3_key::try_key;
+use crate::s3_multipart::div_ceil;
+use crate::{RequestOptions, S3Key, S3};
+
+/// Default number of bytes fetched by each ranged get of a resumable download.
+pub const DEFAULT_DOWNLOAD_PART_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Extension appended to the path of a download to name its state file.
+pub const DOWNLOAD_STATE_EXTENSION: &str = "s3download";
+
+/// Options of a resumable download.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadOptions {
+    /// Bytes fetched by each ranged get, the progress is saved after each of them
+    pub part_size: u64,
+}
+
+impl Default for DownloadOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            part_size: DEFAULT_DOWNLOAD_PART_SIZE,
+        }
+    }
+}
+
+impl DownloadOptions {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn part_size(mut self, part_size: u64) -> Self {
+        self.part_size = part_size.max(1);
+        self
+    }
+}
+
+/// Progress of a resumable download, saved next to the downloaded file.
+///
+/// The download only resumes while the object keeps the same ETag and size, otherwise it starts
+/// over.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DownloadState {
+    pub etag: String,
+    pub size: u64,
+    pub part_size: u64,
+    /// Byte ranges (inclusive) already written to the file
+    pub completed: Vec<(u64, u64)>,
+}
+
+impl DownloadState {
+    #[inline]
+    pub fn new(etag: impl Into<String>, size: u64, part_size: u64) -> Self {
+        Self {
+            etag: etag.into(),
+            size,
+            part_size,
+            completed: vec![],
+        }
+    }
+
+    /// Ranges of `part_size` bytes covering the object, which are not completed yet.
+    ///
+    /// Example:
+    /// ```rust
+    /// use simple_aws_s3::DownloadState;
+    ///
+    /// let mut state = DownloadState::new("\"etag\"", 25, 10);
+    /// assert_eq!(state.pending(), vec![(0, 9), (10, 19), (20, 24)]);
+    ///
+    /// state.completed.push((10, 19));
+    /// assert_eq!(state.pending(), vec![(0, 9), (20, 24)]);
+    /// assert!(!state.is_complete());
+    /// ```
+    #[inline]
+    pub fn pending(&self) -> Vec<(u64, u64)> {
+        let part_size = self.part_size.max(1);
+        (0..div_ceil(self.size, part_size))
+            .map(|i| {
+                let start = i * part_size;
+                (start, (start + part_size).min(self.size) - 1)
+            })
+            .filter(|range| !self.completed.contains(range))
+            .collect()
+    }
+
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.pending().is_empty()
+    }
+}
+
+/// Path of the state file of a download to `path`, e.g. `data.bin.s3download`.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::download_state_path;
+/// use std::path::Path;
+///
+/// assert_eq!(
+///     download_state_path("/tmp/data.bin"),
+///     Path::new("/tmp/data.bin.s3download")
+/// );
+/// ```
+#[inline]
+pub fn download_state_path(path: impl AsRef<Path>) -> PathBuf {
+    let mut state_path = OsString::from(path.as_ref().as_os_str());
+    state_path.push(".");
+    state_path.push(DOWNLOAD_STATE_EXTENSION);
+    PathBuf::from(state_path)
+}
+
+impl S3 {
+    /// Download an object to `path` with ranged gets, recording each completed range in a state
+    /// file (see `download_state_path`) so an interrupted download continues where it stopped.
+    ///
+    /// The ranged gets are sent with `If-Match`, so a download never mixes two versions of an
+    /// object. The state file is removed once the download completes. Returns the size of the
+    /// object.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::{DownloadOptions, S3};
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let options = DownloadOptions::new().part_size(64 * 1024 * 1024);
+    /// // Running this again after a crash only fetches the missing ranges
+    /// let size = s3
+    ///     .download_resumable_with_options("artifacts/build.tar", "/tmp/build.tar", &options)
+    ///     .await?;
+    /// println!("downloaded {} bytes", size);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn download_resumable(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        path: impl AsRef<Path>,
+    ) -> Result<u64, Error> {
+        self.download_resumable_with_options(key, path, &DownloadOptions::default())
+            .await
+    }
+
+    #[inline]
+    pub async fn download_resumable_with_options(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        path: impl AsRef<Path>,
+        options: &DownloadOptions,
+    ) -> Result<u64, Error> {
+        let key = &try_key(key)?;
+        let path = path.as_ref();
+        let state_path = download_state_path(path);
+
+        let req = self.prepare_simple_object_method(key, Method::HEAD)?;
+        let res = self.execute(req).await?;
+        let header = |name| {
+            res.headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default()
+        };
+        let etag = header(ETAG).to_string();
+        // Never mistaken for an empty object
+        let size = header(CONTENT_LENGTH).parse::<u64>().map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Invalid Content-Length {:?} of {}",
+                    header(CONTENT_LENGTH),
+                    key
+                ),
+            )
+        })?;
+
+        let saved = match fs::read(&state_path).await {
+            Ok(data) => serde_json::from_slice::<DownloadState>(&data).ok(),
+            Err(_) => None,
+        };
+        let mut state = match saved {
+            Some(state) if state.etag == etag && state.size == size && path.exists() => state,
+            // No progress, or progress of another version of the object
+            _ => DownloadState::new(etag, size, options.part_size),
+        };
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .await?;
+        file.set_len(size).await?;
+
+        for (start, end) in state.pending() {
+            let options = RequestOptions::new()
+                .range(start, Some(end))
+                .if_match(state.etag.as_str());
+            let req = self.prepare_object_request(key, Method::GET, &options)?;
+            let bytes = self.execute(req).await?.bytes().await?;
+            if bytes.len() as u64 != end - start + 1 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!(
+                        "Got {} bytes of the range {}-{} of {}",
+                        bytes.len(),
+                        start,
+                        end,
+                        key
+                    ),
+                )
+                .into());
+            }
+
+            file.seek(SeekFrom::Start(start)).await?;
+            file.write_all(&bytes).await?;
+            file.sync_data().await?;
+
+            state.completed.push((start, end));
+            save_state(&state_path, &state).await?;
+        }
+
+        file.sync_all().await?;
+        if fs::metadata(&state_path).await.is_ok() {
+            fs::remove_file(&state_path).await?;
+        }
+        Ok(size)
+    }
+}
+
+/// Write the state through a temporary file, so a crash never leaves a truncated state.
+#[inline]
+async fn save_state(state_path: &Path, state: &DownloadState) -> Result<(), Error> {
+    let mut tmp_path = OsString::from(state_path.as_os_str());
+    tmp_path.push(".tmp");
+    fs::write(&tmp_path, serde_json::to_vec(state)?).await?;
+    fs::rename(&tmp_path, state_path).await?;
+    Ok(())
+}
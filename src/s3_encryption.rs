@@ -0,0 +1,182 @@
+use reqwest::Method;
+
+use crate::error::Error;
+use crate::s3_delete::escape_xml;
+use crate::{RequestOptions, S3};
+
+/// Default encryption of the objects uploaded to a bucket.
+///
+/// Ref: https://docs.aws.amazon.com/AmazonS3/latest/userguide/bucket-encryption.html
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::BucketEncryption;
+///
+/// let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <ServerSideEncryptionConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+///   <Rule>
+///     <ApplyServerSideEncryptionByDefault>
+///       <SSEAlgorithm>aws:kms</SSEAlgorithm>
+///       <KMSMasterKeyID>arn:aws:kms:us-east-1:1234/5678example</KMSMasterKeyID>
+///     </ApplyServerSideEncryptionByDefault>
+///     <BucketKeyEnabled>true</BucketKeyEnabled>
+///   </Rule>
+/// </ServerSideEncryptionConfiguration>"#;
+///
+/// let encryption = BucketEncryption::from_xml(body).unwrap();
+/// assert_eq!(encryption, BucketEncryption::kms("arn:aws:kms:us-east-1:1234/5678example").bucket_key(true));
+/// assert!(BucketEncryption::s3_managed().to_xml().contains("<SSEAlgorithm>AES256</SSEAlgorithm>"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BucketEncryption {
+    /// `AES256` (keys managed by S3), `aws:kms` or `aws:kms:dsse`
+    pub algorithm: String,
+    /// Key of KMS encryption, the AWS managed key of S3 if `None`
+    pub kms_key_id: Option<String>,
+    /// Use an S3 Bucket Key, reducing the requests to KMS
+    pub bucket_key_enabled: bool,
+}
+
+impl BucketEncryption {
+    /// Encryption with keys managed by S3 (SSE-S3), the default of new buckets.
+    #[inline]
+    pub fn s3_managed() -> Self {
+        Self {
+            algorithm: "AES256".into(),
+            kms_key_id: None,
+            bucket_key_enabled: false,
+        }
+    }
+
+    /// Encryption with a KMS key (SSE-KMS).
+    #[inline]
+    pub fn kms(key_id: impl Into<String>) -> Self {
+        Self {
+            algorithm: "aws:kms".into(),
+            kms_key_id: Some(key_id.into()),
+            bucket_key_enabled: false,
+        }
+    }
+
+    #[inline]
+    pub fn bucket_key(mut self, enabled: bool) -> Self {
+        self.bucket_key_enabled = enabled;
+        self
+    }
+
+    /// Parse a GetBucketEncryption response, only its first rule is read.
+    #[inline]
+    pub fn from_xml(body: &str) -> Result<Self, Error> {
+        let config: EncryptionConfigurationXml = serde_xml_rs::from_str(body)?;
+        let rule =
+            config.rules.into_iter().next().ok_or_else(|| {
+                Error::InvalidInput("Encryption configuration without rule".into())
+            })?;
+        Ok(Self {
+            algorithm: rule.default.algorithm,
+            kms_key_id: rule.default.kms_key_id,
+            bucket_key_enabled: rule.bucket_key_enabled.unwrap_or(false),
+        })
+    }
+
+    #[inline]
+    pub fn to_xml(&self) -> String {
+        let mut body = String::from(
+            r#"<ServerSideEncryptionConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Rule><ApplyServerSideEncryptionByDefault>"#,
+        );
+        body.push_str(&format!(
+            "<SSEAlgorithm>{}</SSEAlgorithm>",
+            escape_xml(&self.algorithm)
+        ));
+        if let Some(key_id) = &self.kms_key_id {
+            body.push_str(&format!(
+                "<KMSMasterKeyID>{}</KMSMasterKeyID>",
+                escape_xml(key_id)
+            ));
+        }
+        body.push_str(&format!(
+            "</ApplyServerSideEncryptionByDefault><BucketKeyEnabled>{}</BucketKeyEnabled></Rule></ServerSideEncryptionConfiguration>",
+            self.bucket_key_enabled
+        ));
+        body
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct EncryptionConfigurationXml {
+    #[serde(rename = "Rule", default)]
+    rules: Vec<EncryptionRuleXml>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EncryptionRuleXml {
+    #[serde(rename = "ApplyServerSideEncryptionByDefault")]
+    default: EncryptionDefaultXml,
+    #[serde(rename = "BucketKeyEnabled", default)]
+    bucket_key_enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EncryptionDefaultXml {
+    #[serde(rename = "SSEAlgorithm")]
+    algorithm: String,
+    #[serde(rename = "KMSMasterKeyID", default)]
+    kms_key_id: Option<String>,
+}
+
+impl S3 {
+    /// Get the default encryption of the bucket, `None` if it has none (buckets created before
+    /// 2023).
+    #[inline]
+    pub async fn get_bucket_encryption(&self) -> Result<Option<BucketEncryption>, Error> {
+        let req = self.prepare_request(
+            Method::GET,
+            None,
+            &[("encryption", "")],
+            &RequestOptions::default(),
+        )?;
+        match self.execute(req).await {
+            Ok(res) => Ok(Some(BucketEncryption::from_xml(&res.text().await?)?)),
+            // ServerSideEncryptionConfigurationNotFoundError
+            Err(Error::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Set the default encryption of the bucket.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::{BucketEncryption, S3};
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let encryption = BucketEncryption::kms("arn:aws:kms:us-east-1:111122223333:key/1234abcd").bucket_key(true);
+    /// s3.put_bucket_encryption(&encryption).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn put_bucket_encryption(&self, encryption: &BucketEncryption) -> Result<(), Error> {
+        let req = self.prepare_xml_request(
+            Method::PUT,
+            None,
+            &[("encryption", "")],
+            encryption.to_xml(),
+        )?;
+        self.execute(req).await?;
+        Ok(())
+    }
+
+    /// Remove the default encryption of the bucket, S3 then falls back to SSE-S3.
+    #[inline]
+    pub async fn delete_bucket_encryption(&self) -> Result<(), Error> {
+        let req = self.prepare_request(
+            Method::DELETE,
+            None,
+            &[("encryption", "")],
+            &RequestOptions::default(),
+        )?;
+        self.execute(req).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use reqwest::Method;
+
+use crate::error::Error;
+use crate::{ListObjectsOptions, RequestOptions, S3Key, S3};
+
+/// Helpers to work with "folders": prefixes ending with `/`.
+impl S3 {
+    /// List the direct sub folders of `prefix` (use `""` for the root of the bucket).
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::S3;
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// s3.create_folder("photos/2021").await?;
+    /// let folders = s3.list_folders("photos/").await?;
+    /// assert!(folders.contains(&"photos/2021/".to_string()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn list_folders(&self, prefix: &str) -> Result<Vec<String>, Error> {
+        let mut options = ListObjectsOptions::new()
+            .prefix(folder_prefix(prefix))
+            .delimiter("/");
+        let mut folders = vec![];
+
+        loop {
+            let page = self.list_objects_v2(&options).await?;
+            folders.extend(page.common_prefixes.into_iter().map(|p| p.prefix));
+            match page.next_continuation_token {
+                Some(token) if page.is_truncated => options.continuation_token = Some(token),
+                _ => return Ok(folders),
+            }
+        }
+    }
+
+    /// Create a folder, a zero byte object whose key ends with `/`.
+    #[inline]
+    pub async fn create_folder(&self, prefix: &str) -> Result<(), Error> {
+        let key = S3Key::new(folder_prefix(prefix))?;
+        let req = self.prepare_request(Method::PUT, Some(&key), &[], &RequestOptions::default())?;
+        self.execute(req).await?;
+        Ok(())
+    }
+
+    /// Total size in bytes of the objects under `prefix`, including sub folders.
+    #[inline]
+    pub async fn folder_size(&self, prefix: &str) -> Result<u64, Error> {
+        let options = ListObjectsOptions::new().prefix(folder_prefix(prefix));
+        let objects = self.list_all_objects_v2(&options).await?;
+        Ok(objects.iter().map(|o| o.size).sum())
+    }
+}
+
+/// `prefix` with a trailing `/`, or `""` for the root of the bucket.
+#[inline]
+fn folder_prefix(prefix: &str) -> String {
+    if prefix.is_empty() || prefix.ends_with('/') {
+        prefix.to_string()
+    } else {
+        format!("{}/", prefix)
+    }
+}
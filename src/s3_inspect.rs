@@ -0,0 +1,203 @@
+use std::convert::TryInto;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::error::Error;
+use crate::s3_key::try_key;
+use crate::{
+    GetObjectOutput, HeadObjectOutput, RequestOptions, S3Key, UploadExpectation, UploadMismatch, S3,
+};
+
+/// Default prefix rejected uploads are moved under.
+pub const DEFAULT_QUARANTINE_PREFIX: &str = "quarantine/";
+
+/// Future returned by `UploadInspector::inspect`.
+pub type InspectionFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<InspectionVerdict, Error>> + Send + 'a>>;
+
+/// Verdict of an `UploadInspector` on an object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InspectionVerdict {
+    Clean,
+    /// The object must be quarantined, for this reason
+    Rejected(String),
+}
+
+/// Inspection of the content of uploaded objects, e.g. an antivirus or MIME sniffing.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::{GetObjectOutput, InspectionFuture, InspectionVerdict, UploadInspector};
+///
+/// /// Reject uploads which are not PNG files, whatever their content type
+/// struct PngSniffer;
+///
+/// impl UploadInspector for PngSniffer {
+///     fn inspect<'a>(&'a self, _key: &'a str, mut object: GetObjectOutput) -> InspectionFuture<'a> {
+///         Box::pin(async move {
+///             let head = object.chunk().await?.unwrap_or_default();
+///             if head.starts_with(b"\x89PNG\r\n\x1a\n") {
+///                 Ok(InspectionVerdict::Clean)
+///             } else {
+///                 Ok(InspectionVerdict::Rejected("not a png file".into()))
+///             }
+///         })
+///     }
+/// }
+/// ```
+pub trait UploadInspector: Send + Sync {
+    /// Inspect the object `key`, streaming its body from `object`.
+    fn inspect<'a>(&'a self, key: &'a str, object: GetObjectOutput) -> InspectionFuture<'a>;
+}
+
+/// Checks run on an object uploaded by a client: its expectation first, then each inspector
+/// on its content. A rejected object is quarantined: moved under `quarantine_prefix`.
+#[derive(Clone)]
+pub struct UploadPipeline {
+    pub expectation: UploadExpectation,
+    pub quarantine_prefix: String,
+    inspectors: Vec<Arc<dyn UploadInspector>>,
+}
+
+impl UploadPipeline {
+    #[inline]
+    pub fn new(expectation: UploadExpectation) -> Self {
+        Self {
+            expectation,
+            quarantine_prefix: DEFAULT_QUARANTINE_PREFIX.to_string(),
+            inspectors: vec![],
+        }
+    }
+
+    /// Add an inspector, inspectors run in the order they are added.
+    #[inline]
+    pub fn inspector(mut self, inspector: impl UploadInspector + 'static) -> Self {
+        self.inspectors.push(Arc::new(inspector));
+        self
+    }
+
+    #[inline]
+    pub fn quarantine_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.quarantine_prefix = prefix.into();
+        self
+    }
+}
+
+/// Why an upload was quarantined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RejectionReason {
+    /// The object does not match the expectation of the pipeline
+    Mismatch(Vec<UploadMismatch>),
+    /// An inspector rejected the content of the object
+    Inspection(String),
+}
+
+/// Result of `process_client_upload`.
+#[derive(Debug, Clone)]
+pub enum UploadOutcome {
+    /// The object was not uploaded
+    Missing,
+    /// The object passed every check
+    Accepted(Box<HeadObjectOutput>),
+    /// The object was moved to `key`
+    Quarantined {
+        key: String,
+        reason: RejectionReason,
+    },
+}
+
+impl S3 {
+    /// Verify an object uploaded by a client, inspect its content, and quarantine it when
+    /// rejected.
+    ///
+    /// Inspectors get the verified version of the object: if it is replaced meanwhile, the
+    /// inspection fails with a `412 Precondition Failed` error.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::{UploadExpectation, UploadOutcome, UploadPipeline, S3};
+    /// # use simple_aws_s3::{GetObjectOutput, InspectionFuture, InspectionVerdict, UploadInspector};
+    /// # struct Antivirus;
+    /// # impl UploadInspector for Antivirus {
+    /// #     fn inspect<'a>(&'a self, _: &'a str, _: GetObjectOutput) -> InspectionFuture<'a> {
+    /// #         Box::pin(async { Ok(InspectionVerdict::Clean) })
+    /// #     }
+    /// # }
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let pipeline = UploadPipeline::new(UploadExpectation::new().content_length(10485760))
+    ///     .inspector(Antivirus);
+    /// match s3.process_client_upload("attachments/42.pdf", &pipeline).await? {
+    ///     UploadOutcome::Accepted(head) => println!("accepted {:?} bytes", head.content_length),
+    ///     UploadOutcome::Quarantined { key, reason } => eprintln!("moved to {}: {:?}", key, reason),
+    ///     UploadOutcome::Missing => eprintln!("not uploaded"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn process_client_upload(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        pipeline: &UploadPipeline,
+    ) -> Result<UploadOutcome, Error> {
+        let key = &try_key(key)?;
+        let verification = self
+            .verify_client_upload(key, &pipeline.expectation)
+            .await?;
+        let head = match verification.head {
+            Some(head) if verification.mismatches.is_empty() => head,
+            Some(_) => {
+                let reason = RejectionReason::Mismatch(verification.mismatches);
+                return self
+                    .quarantine(key, &pipeline.quarantine_prefix, reason)
+                    .await;
+            }
+            None => return Ok(UploadOutcome::Missing),
+        };
+
+        let mut options = RequestOptions::new();
+        if let Some(etag) = &head.etag {
+            options = options.if_match(etag.as_str());
+        }
+        for inspector in &pipeline.inspectors {
+            let object = match self.get_object_with_options(key, &options).await? {
+                Some(object) => object,
+                None => return Ok(UploadOutcome::Missing),
+            };
+            if let InspectionVerdict::Rejected(reason) = inspector.inspect(key, object).await? {
+                let reason = RejectionReason::Inspection(reason);
+                return self
+                    .quarantine(key, &pipeline.quarantine_prefix, reason)
+                    .await;
+            }
+        }
+        Ok(UploadOutcome::Accepted(Box::new(head)))
+    }
+
+    /// Move `key` under `prefix`, e.g. `uploads/a.png` to `quarantine/uploads/a.png`, and
+    /// return its new key.
+    #[inline]
+    pub async fn quarantine_object(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        prefix: &str,
+    ) -> Result<String, Error> {
+        let key = &try_key(key)?;
+        let quarantined = S3Key::lenient(format!("{}{}", prefix, key))?;
+        self.copy_object(key, &quarantined).await?;
+        self.delete_object(key).await?;
+        Ok(quarantined.into_string())
+    }
+
+    #[inline]
+    async fn quarantine(
+        &self,
+        key: &S3Key,
+        prefix: &str,
+        reason: RejectionReason,
+    ) -> Result<UploadOutcome, Error> {
+        let key = self.quarantine_object(key, prefix).await?;
+        Ok(UploadOutcome::Quarantined { key, reason })
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+use std::io;
+
+use chrono::{DateTime, TimeZone, Utc};
+use futures_util::stream::{self, Stream, StreamExt, TryStreamExt};
+use percent_encoding::percent_decode_str;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::{S3Key, S3};
+
+/// Decompress a gzipped inventory file, e.g. with the `flate2` crate:
+/// `|gz| { let mut csv = vec![]; flate2::read::GzDecoder::new(gz).read_to_end(&mut csv)?; Ok(csv) }`.
+pub type Gunzip = fn(&[u8]) -> io::Result<Vec<u8>>;
+
+/// `manifest.json` of an S3 Inventory report.
+///
+/// Ref: https://docs.aws.amazon.com/AmazonS3/latest/userguide/storage-inventory-location.html
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::InventoryManifest;
+///
+/// let json = r#"{
+///     "sourceBucket": "example-source-bucket",
+///     "destinationBucket": "arn:aws:s3:::example-inventory-destination-bucket",
+///     "version": "2016-11-30",
+///     "creationTimestamp": "1514944800000",
+///     "fileFormat": "CSV",
+///     "fileSchema": "Bucket, Key, VersionId, IsLatest, IsDeleteMarker, Size, LastModifiedDate, ETag, StorageClass",
+///     "files": [
+///         {
+///             "key": "Inventory/example-source-bucket/2016-11-06T21-32Z/files/939c6d46-85a9-4ba8-aa2b-eabc1ad7b0e4.csv.gz",
+///             "size": 2147,
+///             "MD5checksum": "f11166069f1990abeb9c97ace9cdfabc"
+///         }
+///     ]
+/// }"#;
+///
+/// let manifest: InventoryManifest = serde_json::from_str(json).unwrap();
+/// assert_eq!(manifest.columns()[1], "Key");
+/// assert_eq!(manifest.created_at().unwrap().to_rfc3339(), "2018-01-03T02:00:00+00:00");
+/// assert_eq!(manifest.files[0].size, 2147);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryManifest {
+    pub source_bucket: String,
+    /// Arn of the bucket of the report
+    pub destination_bucket: String,
+    pub version: String,
+    /// Milliseconds since the epoch
+    pub creation_timestamp: String,
+    /// `CSV`, `ORC` or `Parquet`, only `CSV` reports are read by this crate
+    pub file_format: String,
+    /// Comma separated columns of the files
+    pub file_schema: String,
+    pub files: Vec<InventoryFile>,
+}
+
+impl InventoryManifest {
+    /// Columns of the files, e.g. `["Bucket", "Key", "Size"]`.
+    #[inline]
+    pub fn columns(&self) -> Vec<&str> {
+        self.file_schema.split(',').map(str::trim).collect()
+    }
+
+    #[inline]
+    pub fn created_at(&self) -> Option<DateTime<Utc>> {
+        let millis = self.creation_timestamp.parse().ok()?;
+        Some(Utc.timestamp_millis(millis))
+    }
+}
+
+/// File of an inventory report, in the destination bucket.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InventoryFile {
+    pub key: String,
+    pub size: u64,
+    #[serde(rename = "MD5checksum")]
+    pub md5_checksum: String,
+}
+
+/// Object of an inventory report. Columns missing from the report or empty are `None`, every
+/// column is in `fields` as reported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InventoryRecord {
+    pub bucket: String,
+    /// Key of the object, url decoded
+    pub key: String,
+    pub version_id: Option<String>,
+    pub is_latest: Option<bool>,
+    pub is_delete_marker: Option<bool>,
+    pub size: Option<u64>,
+    pub last_modified: Option<DateTime<Utc>>,
+    pub etag: Option<String>,
+    pub storage_class: Option<String>,
+    /// Values by column, e.g. `EncryptionStatus`
+    pub fields: BTreeMap<String, String>,
+}
+
+impl InventoryRecord {
+    /// Parse a line of a CSV inventory file with `columns`, see `InventoryManifest::columns`.
+    ///
+    /// Example:
+    /// ```rust
+    /// use simple_aws_s3::InventoryRecord;
+    ///
+    /// let columns = ["Bucket", "Key", "Size", "LastModifiedDate", "ETag", "EncryptionStatus"];
+    /// let line = r#""example-source-bucket","photos/my+cat%2B1.jpg","4096","2016-11-06T21:32:55.000Z","599bab3ed2c697f1d26842727561fd94","SSE-S3""#;
+    ///
+    /// let record = InventoryRecord::parse(&columns, line).unwrap();
+    /// assert_eq!(record.key, "photos/my cat+1.jpg");
+    /// assert_eq!(record.size, Some(4096));
+    /// assert_eq!(record.last_modified.unwrap().to_rfc3339(), "2016-11-06T21:32:55+00:00");
+    /// assert_eq!(record.version_id, None);
+    /// assert_eq!(record.fields["EncryptionStatus"], "SSE-S3");
+    /// ```
+    pub fn parse(columns: &[&str], line: &str) -> Result<Self, Error> {
+        let values = split_csv(line)?;
+        if values.len() != columns.len() {
+            return Err(Error::InvalidInput(format!(
+                "Inventory record of {} columns instead of {}: {:?}",
+                values.len(),
+                columns.len(),
+                line
+            )));
+        }
+        let fields: BTreeMap<String, String> = columns
+            .iter()
+            .map(|column| column.to_string())
+            .zip(values)
+            .collect();
+
+        let field = |column: &str| {
+            fields
+                .get(column)
+                .filter(|value| !value.is_empty())
+                .cloned()
+        };
+        let flag = |column: &str| field(column).map(|value| value == "true");
+        let key = field("Key")
+            .ok_or_else(|| {
+                Error::InvalidInput(format!("Inventory record without key: {:?}", line))
+            })?
+            .replace('+', " ");
+
+        Ok(Self {
+            bucket: field("Bucket").unwrap_or_default(),
+            key: percent_decode_str(&key).decode_utf8_lossy().into_owned(),
+            version_id: field("VersionId"),
+            is_latest: flag("IsLatest"),
+            is_delete_marker: flag("IsDeleteMarker"),
+            size: field("Size").and_then(|value| value.parse().ok()),
+            last_modified: field("LastModifiedDate")
+                .and_then(|value| DateTime::parse_from_rfc3339(&value).ok())
+                .map(|date| date.with_timezone(&Utc)),
+            etag: field("ETag"),
+            storage_class: field("StorageClass"),
+            fields,
+        })
+    }
+}
+
+/// Parse the records of a CSV inventory file, skipping its blank lines.
+#[inline]
+pub fn parse_inventory_csv<'a>(
+    columns: &'a [&str],
+    csv: &'a str,
+) -> impl Iterator<Item = Result<InventoryRecord, Error>> + 'a {
+    csv.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(move |line| InventoryRecord::parse(columns, line))
+}
+
+/// Split a CSV line into its values, unquoting them (`""` is an escaped quote).
+fn split_csv(line: &str) -> Result<Vec<String>, Error> {
+    let mut values = vec![];
+    let mut value = String::new();
+    let mut quoted = false;
+    let mut chars = line.trim_end_matches(['\r', '\n']).chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                value.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => values.push(std::mem::take(&mut value)),
+            c => value.push(c),
+        }
+    }
+    if quoted {
+        return Err(Error::InvalidInput(format!(
+            "Unterminated value in inventory record: {:?}",
+            line
+        )));
+    }
+    values.push(value);
+    Ok(values)
+}
+
+impl S3 {
+    /// Get the manifest of an inventory report, e.g.
+    /// `Inventory/example-source-bucket/2016-11-06T21-32Z/manifest.json`. A missing manifest is
+    /// `Error::NotFound`.
+    #[inline]
+    pub async fn get_inventory_manifest(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+    ) -> Result<InventoryManifest, Error> {
+        let req = self.prepare_simple_object_method(key, Method::GET)?;
+        Ok(serde_json::from_slice(
+            &self.execute(req).await?.bytes().await?,
+        )?)
+    }
+
+    /// Stream the records of a CSV inventory report, downloading its files one at a time. The
+    /// client must be of the destination bucket of the report. Gzipped files (`.csv.gz`, as S3
+    /// writes them) need `gunzip`.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use futures_util::StreamExt;
+    /// use simple_aws_s3::S3;
+    ///
+    /// # fn gunzip(gz: &[u8]) -> std::io::Result<Vec<u8>> { Ok(gz.to_vec()) }
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let manifest = s3
+    ///     .get_inventory_manifest("Inventory/example-source-bucket/2016-11-06T21-32Z/manifest.json")
+    ///     .await?;
+    /// let records = s3.inventory_records(&manifest, Some(gunzip));
+    /// futures_util::pin_mut!(records);
+    /// while let Some(record) = records.next().await {
+    ///     let record = record?;
+    ///     println!("{} {:?}", record.key, record.size);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn inventory_records(
+        &self,
+        manifest: &InventoryManifest,
+        gunzip: Option<Gunzip>,
+    ) -> impl Stream<Item = Result<InventoryRecord, Error>> + Send + 'static {
+        let s3 = self.clone();
+        let columns: Vec<String> = manifest.columns().into_iter().map(String::from).collect();
+        let (files, unsupported) = if manifest.file_format.eq_ignore_ascii_case("CSV") {
+            (manifest.files.clone(), None)
+        } else {
+            let e = Error::InvalidInput(format!(
+                "Unsupported inventory format: {}",
+                manifest.file_format
+            ));
+            (vec![], Some(Err(e)))
+        };
+
+        let records = stream::iter(files.into_iter().map(Ok::<_, Error>))
+            .and_then(move |file| {
+                let s3 = s3.clone();
+                let columns = columns.clone();
+                async move {
+                    let req = s3.prepare_simple_object_method(
+                        S3Key::lenient(file.key.as_str())?,
+                        Method::GET,
+                    )?;
+                    let data = s3.execute(req).await?.bytes().await?;
+                    let csv = if file.key.ends_with(".gz") {
+                        let gunzip = gunzip.ok_or_else(|| {
+                            Error::InvalidInput(format!(
+                                "Gzipped inventory file without gunzip: {}",
+                                file.key
+                            ))
+                        })?;
+                        gunzip(&data)?
+                    } else {
+                        data.to_vec()
+                    };
+                    let csv = String::from_utf8(csv).map_err(|e| {
+                        Error::InvalidInput(format!("Inventory file {}: {}", file.key, e))
+                    })?;
+                    let columns: Vec<&str> = columns.iter().map(String::as_str).collect();
+                    let records: Vec<_> = parse_inventory_csv(&columns, &csv).collect();
+                    Ok(stream::iter(records))
+                }
+            })
+            .try_flatten();
+        stream::iter(unsupported).chain(records)
+    }
+}
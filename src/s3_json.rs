@@ -0,0 +1,96 @@
+use std::convert::TryInto;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::{PutObjectOutput, RequestOptions, S3Key, S3};
+
+impl S3 {
+    /// Get a JSON document and deserialize it, `None` if it does not exist.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::S3;
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct Settings {
+    ///     theme: String,
+    /// }
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// if let Some(settings) = s3.get_object_json::<Settings>("users/42/settings.json").await? {
+    ///     println!("theme: {}", settings.theme);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn get_object_json<T: DeserializeOwned>(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+    ) -> Result<Option<T>, Error> {
+        match self.get_object(key).await? {
+            Some(res) => Ok(Some(serde_json::from_slice(&res.bytes().await?)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Same as `get_object_json`, failing with `Error::BodyTooLarge` when the document exceeds
+    /// `max` bytes.
+    #[inline]
+    pub async fn get_object_json_limited<T: DeserializeOwned>(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        max: u64,
+    ) -> Result<Option<T>, Error> {
+        match self.get_object_bytes_limited(key, max).await? {
+            Some(body) => Ok(Some(serde_json::from_slice(&body)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Serialize `value` and upload it as an `application/json` object.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::S3;
+    ///
+    /// #[derive(serde::Serialize)]
+    /// struct Settings {
+    ///     theme: String,
+    /// }
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let settings = Settings { theme: "dark".into() };
+    /// let output = s3.put_object_json("users/42/settings.json", &settings).await?;
+    /// println!("uploaded {:?}", output.etag);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn put_object_json<T: Serialize + ?Sized>(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        value: &T,
+    ) -> Result<PutObjectOutput, Error> {
+        self.put_object_json_with_options(key, value, &RequestOptions::default())
+            .await
+    }
+
+    /// Same as `put_object_json`, with options such as metadata or an ACL. The content type is
+    /// always `application/json`.
+    #[inline]
+    pub async fn put_object_json_with_options<T: Serialize + ?Sized>(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        value: &T,
+        options: &RequestOptions,
+    ) -> Result<PutObjectOutput, Error> {
+        let body = serde_json::to_vec(value)?;
+        let options = options
+            .clone()
+            .content_type(mime::APPLICATION_JSON.as_ref());
+        self.put_object_with_options(key, body, &options).await
+    }
+}
@@ -0,0 +1,416 @@
+use reqwest::Method;
+use serde::de::IgnoredAny;
+
+use crate::error::Error;
+use crate::s3_delete::escape_xml;
+use crate::{RequestOptions, S3};
+
+/// Transition of objects to another storage class, some days after their creation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LifecycleTransition {
+    pub days: u32,
+    /// e.g. `STANDARD_IA`, `GLACIER` or `DEEP_ARCHIVE`
+    pub storage_class: String,
+}
+
+/// Lifecycle rule of a bucket, applied to the objects under a prefix.
+///
+/// Only rules filtering on a prefix, with actions after a number of days, are modeled. The other
+/// elements of a rule read from a bucket (tag and size filters, dates, delete markers...) are
+/// listed in `unsupported`, so such a rule never equals a declared one, e.g. for
+/// `S3::ensure_bucket` to rewrite it. They are not written back by `to_xml`.
+///
+/// Ref: https://docs.aws.amazon.com/AmazonS3/latest/userguide/object-lifecycle-mgmt.html
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::LifecycleRule;
+///
+/// let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <LifecycleConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+///   <Rule>
+///     <ID>archive-logs</ID>
+///     <Filter>
+///       <Prefix>logs/</Prefix>
+///     </Filter>
+///     <Status>Enabled</Status>
+///     <Transition>
+///       <Days>30</Days>
+///       <StorageClass>GLACIER</StorageClass>
+///     </Transition>
+///     <Expiration>
+///       <Days>365</Days>
+///     </Expiration>
+///     <AbortIncompleteMultipartUpload>
+///       <DaysAfterInitiation>7</DaysAfterInitiation>
+///     </AbortIncompleteMultipartUpload>
+///   </Rule>
+/// </LifecycleConfiguration>"#;
+///
+/// let rules = LifecycleRule::from_xml(body).unwrap();
+/// assert_eq!(
+///     rules,
+///     vec![LifecycleRule::new("archive-logs", "logs/")
+///         .transition(30, "GLACIER")
+///         .expiration_days(365)
+///         .abort_incomplete_multipart_upload_days(7)]
+/// );
+/// assert!(LifecycleRule::to_xml(&rules).contains("<Filter><Prefix>logs/</Prefix></Filter><Status>Enabled</Status>"));
+///
+/// let body = r#"<LifecycleConfiguration>
+///   <Rule>
+///     <ID>expire-tagged-tmp</ID>
+///     <Filter><And><Prefix>tmp/</Prefix><Tag><Key>x</Key><Value>1</Value></Tag></And></Filter>
+///     <Status>Enabled</Status>
+///     <Expiration><Days>1</Days></Expiration>
+///   </Rule>
+/// </LifecycleConfiguration>"#;
+/// let rules = LifecycleRule::from_xml(body).unwrap();
+/// assert_eq!(rules[0].unsupported, vec!["Filter/And/Tag"]);
+/// assert_ne!(rules[0], LifecycleRule::new("expire-tagged-tmp", "tmp/").expiration_days(1));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LifecycleRule {
+    pub id: String,
+    /// Prefix of the objects of the rule, empty for the whole bucket
+    pub prefix: String,
+    pub enabled: bool,
+    pub transitions: Vec<LifecycleTransition>,
+    /// Delete (or mark as deleted in versioned buckets) objects this many days old
+    pub expiration_days: Option<u32>,
+    /// Delete noncurrent versions this many days after they became noncurrent
+    pub noncurrent_version_expiration_days: Option<u32>,
+    /// Abort multipart uploads this many days after they started
+    pub abort_incomplete_multipart_upload_days: Option<u32>,
+    /// Paths of the elements of the rule which are not modeled, e.g. `Filter/Tag`
+    pub unsupported: Vec<String>,
+}
+
+impl LifecycleRule {
+    /// Enabled rule without action, see the other builders.
+    #[inline]
+    pub fn new(id: impl Into<String>, prefix: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            prefix: prefix.into(),
+            enabled: true,
+            transitions: vec![],
+            expiration_days: None,
+            noncurrent_version_expiration_days: None,
+            abort_incomplete_multipart_upload_days: None,
+            unsupported: vec![],
+        }
+    }
+
+    #[inline]
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    #[inline]
+    pub fn transition(mut self, days: u32, storage_class: impl Into<String>) -> Self {
+        self.transitions.push(LifecycleTransition {
+            days,
+            storage_class: storage_class.into(),
+        });
+        self
+    }
+
+    #[inline]
+    pub fn expiration_days(mut self, days: u32) -> Self {
+        self.expiration_days = Some(days);
+        self
+    }
+
+    #[inline]
+    pub fn noncurrent_version_expiration_days(mut self, days: u32) -> Self {
+        self.noncurrent_version_expiration_days = Some(days);
+        self
+    }
+
+    #[inline]
+    pub fn abort_incomplete_multipart_upload_days(mut self, days: u32) -> Self {
+        self.abort_incomplete_multipart_upload_days = Some(days);
+        self
+    }
+
+    /// Parse the rules of a GetBucketLifecycleConfiguration response.
+    #[inline]
+    pub fn from_xml(body: &str) -> Result<Vec<Self>, Error> {
+        let config: LifecycleConfigurationXml = serde_xml_rs::from_str(body)?;
+        Ok(config.rules.into_iter().map(Self::from).collect())
+    }
+
+    #[inline]
+    pub fn to_xml(rules: &[Self]) -> String {
+        let mut body = String::from(
+            r#"<LifecycleConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+        );
+        for rule in rules {
+            body.push_str(&format!(
+                "<Rule><ID>{}</ID><Filter><Prefix>{}</Prefix></Filter><Status>{}</Status>",
+                escape_xml(&rule.id),
+                escape_xml(&rule.prefix),
+                if rule.enabled { "Enabled" } else { "Disabled" },
+            ));
+            for transition in &rule.transitions {
+                body.push_str(&format!(
+                    "<Transition><Days>{}</Days><StorageClass>{}</StorageClass></Transition>",
+                    transition.days,
+                    escape_xml(&transition.storage_class),
+                ));
+            }
+            if let Some(days) = rule.expiration_days {
+                body.push_str(&format!("<Expiration><Days>{}</Days></Expiration>", days));
+            }
+            if let Some(days) = rule.noncurrent_version_expiration_days {
+                body.push_str(&format!(
+                    "<NoncurrentVersionExpiration><NoncurrentDays>{}</NoncurrentDays></NoncurrentVersionExpiration>",
+                    days
+                ));
+            }
+            if let Some(days) = rule.abort_incomplete_multipart_upload_days {
+                body.push_str(&format!(
+                    "<AbortIncompleteMultipartUpload><DaysAfterInitiation>{}</DaysAfterInitiation></AbortIncompleteMultipartUpload>",
+                    days
+                ));
+            }
+            body.push_str("</Rule>");
+        }
+        body.push_str("</LifecycleConfiguration>");
+        body
+    }
+}
+
+impl From<LifecycleRuleXml> for LifecycleRule {
+    fn from(rule: LifecycleRuleXml) -> Self {
+        let unsupported = rule.unsupported();
+        let LifecycleFilterXml { prefix, and, .. } = rule.filter.unwrap_or_default();
+        let prefix = prefix
+            .or_else(|| and.and_then(|and| and.prefix))
+            .or(rule.prefix)
+            .unwrap_or_default();
+        Self {
+            id: rule.id.unwrap_or_default(),
+            prefix,
+            enabled: rule.status == "Enabled",
+            transitions: rule
+                .transitions
+                .into_iter()
+                .filter_map(|transition| {
+                    Some(LifecycleTransition {
+                        days: transition.days?,
+                        storage_class: transition.storage_class,
+                    })
+                })
+                .collect(),
+            expiration_days: rule.expiration.and_then(|expiration| expiration.days),
+            noncurrent_version_expiration_days: rule
+                .noncurrent_version_expiration
+                .and_then(|expiration| expiration.noncurrent_days),
+            abort_incomplete_multipart_upload_days: rule
+                .abort_incomplete_multipart_upload
+                .map(|abort| abort.days_after_initiation),
+            unsupported,
+        }
+    }
+}
+
+impl LifecycleRuleXml {
+    /// Paths of the elements present in the rule which `LifecycleRule` does not model.
+    fn unsupported(&self) -> Vec<String> {
+        let mut unsupported = vec![];
+        let mut check = |present: bool, path: &str| {
+            if present {
+                unsupported.push(path.to_string());
+            }
+        };
+        if let Some(filter) = &self.filter {
+            check(!filter.tags.is_empty(), "Filter/Tag");
+            check(
+                filter.size_greater_than.is_some(),
+                "Filter/ObjectSizeGreaterThan",
+            );
+            check(filter.size_less_than.is_some(), "Filter/ObjectSizeLessThan");
+            if let Some(and) = &filter.and {
+                check(!and.tags.is_empty(), "Filter/And/Tag");
+                check(
+                    and.size_greater_than.is_some(),
+                    "Filter/And/ObjectSizeGreaterThan",
+                );
+                check(
+                    and.size_less_than.is_some(),
+                    "Filter/And/ObjectSizeLessThan",
+                );
+            }
+        }
+        for transition in &self.transitions {
+            check(transition.days.is_none(), "Transition/Date");
+        }
+        if let Some(expiration) = &self.expiration {
+            check(expiration.date.is_some(), "Expiration/Date");
+            check(
+                expiration.expired_object_delete_marker.is_some(),
+                "Expiration/ExpiredObjectDeleteMarker",
+            );
+        }
+        if let Some(expiration) = &self.noncurrent_version_expiration {
+            check(
+                expiration.newer_noncurrent_versions.is_some(),
+                "NoncurrentVersionExpiration/NewerNoncurrentVersions",
+            );
+        }
+        check(
+            !self.noncurrent_version_transitions.is_empty(),
+            "NoncurrentVersionTransition",
+        );
+        unsupported
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LifecycleConfigurationXml {
+    #[serde(rename = "Rule", default)]
+    rules: Vec<LifecycleRuleXml>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct LifecycleRuleXml {
+    #[serde(rename = "ID", default)]
+    id: Option<String>,
+    /// Prefix of the rules written before filters
+    #[serde(default)]
+    prefix: Option<String>,
+    #[serde(default)]
+    filter: Option<LifecycleFilterXml>,
+    status: String,
+    #[serde(rename = "Transition", default)]
+    transitions: Vec<LifecycleTransitionXml>,
+    #[serde(default)]
+    expiration: Option<LifecycleExpirationXml>,
+    #[serde(default)]
+    noncurrent_version_expiration: Option<NoncurrentVersionExpirationXml>,
+    #[serde(rename = "NoncurrentVersionTransition", default)]
+    noncurrent_version_transitions: Vec<IgnoredAny>,
+    #[serde(default)]
+    abort_incomplete_multipart_upload: Option<AbortIncompleteMultipartUploadXml>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct LifecycleFilterXml {
+    #[serde(default)]
+    prefix: Option<String>,
+    #[serde(default)]
+    and: Option<LifecycleFilterAndXml>,
+    #[serde(rename = "Tag", default)]
+    tags: Vec<IgnoredAny>,
+    #[serde(rename = "ObjectSizeGreaterThan", default)]
+    size_greater_than: Option<IgnoredAny>,
+    #[serde(rename = "ObjectSizeLessThan", default)]
+    size_less_than: Option<IgnoredAny>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct LifecycleFilterAndXml {
+    #[serde(default)]
+    prefix: Option<String>,
+    #[serde(rename = "Tag", default)]
+    tags: Vec<IgnoredAny>,
+    #[serde(rename = "ObjectSizeGreaterThan", default)]
+    size_greater_than: Option<IgnoredAny>,
+    #[serde(rename = "ObjectSizeLessThan", default)]
+    size_less_than: Option<IgnoredAny>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct LifecycleTransitionXml {
+    #[serde(default)]
+    days: Option<u32>,
+    storage_class: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct LifecycleExpirationXml {
+    #[serde(default)]
+    days: Option<u32>,
+    #[serde(default)]
+    date: Option<IgnoredAny>,
+    #[serde(default)]
+    expired_object_delete_marker: Option<IgnoredAny>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct NoncurrentVersionExpirationXml {
+    #[serde(default)]
+    noncurrent_days: Option<u32>,
+    #[serde(default)]
+    newer_noncurrent_versions: Option<IgnoredAny>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct AbortIncompleteMultipartUploadXml {
+    days_after_initiation: u32,
+}
+
+impl S3 {
+    /// Get the lifecycle rules of the bucket, empty if it has none.
+    #[inline]
+    pub async fn get_bucket_lifecycle(&self) -> Result<Vec<LifecycleRule>, Error> {
+        let req = self.prepare_request(
+            Method::GET,
+            None,
+            &[("lifecycle", "")],
+            &RequestOptions::default(),
+        )?;
+        match self.execute(req).await {
+            Ok(res) => LifecycleRule::from_xml(&res.text().await?),
+            // NoSuchLifecycleConfiguration
+            Err(Error::NotFound(_)) => Ok(vec![]),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Replace the lifecycle rules of the bucket.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::{LifecycleRule, S3};
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let rule = LifecycleRule::new("expire-tmp", "tmp/").expiration_days(1);
+    /// s3.put_bucket_lifecycle(&[rule]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn put_bucket_lifecycle(&self, rules: &[LifecycleRule]) -> Result<(), Error> {
+        let req = self.prepare_xml_request(
+            Method::PUT,
+            None,
+            &[("lifecycle", "")],
+            LifecycleRule::to_xml(rules),
+        )?;
+        self.execute(req).await?;
+        Ok(())
+    }
+
+    #[inline]
+    pub async fn delete_bucket_lifecycle(&self) -> Result<(), Error> {
+        let req = self.prepare_request(
+            Method::DELETE,
+            None,
+            &[("lifecycle", "")],
+            &RequestOptions::default(),
+        )?;
+        self.execute(req).await?;
+        Ok(())
+    }
+}
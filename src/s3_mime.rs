@@ -0,0 +1,137 @@
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+/// Default content type for keys without a known extension.
+pub const DEFAULT_CONTENT_TYPE: &str = "application/octet-stream";
+
+const CONTENT_TYPES: &[(&str, &str)] = &[
+    ("avif", "image/avif"),
+    ("bmp", "image/bmp"),
+    ("css", "text/css"),
+    ("csv", "text/csv"),
+    ("doc", "application/msword"),
+    (
+        "docx",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    ),
+    ("gif", "image/gif"),
+    ("gz", "application/gzip"),
+    ("htm", "text/html"),
+    ("html", "text/html"),
+    ("ico", "image/x-icon"),
+    ("jpeg", "image/jpeg"),
+    ("jpg", "image/jpeg"),
+    ("js", "application/javascript"),
+    ("json", "application/json"),
+    ("md", "text/markdown"),
+    ("mjs", "application/javascript"),
+    ("mov", "video/quicktime"),
+    ("mp3", "audio/mpeg"),
+    ("mp4", "video/mp4"),
+    ("ogg", "audio/ogg"),
+    ("otf", "font/otf"),
+    ("pdf", "application/pdf"),
+    ("png", "image/png"),
+    ("ppt", "application/vnd.ms-powerpoint"),
+    (
+        "pptx",
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+    ),
+    ("svg", "image/svg+xml"),
+    ("tar", "application/x-tar"),
+    ("tif", "image/tiff"),
+    ("tiff", "image/tiff"),
+    ("ttf", "font/ttf"),
+    ("txt", "text/plain"),
+    ("wasm", "application/wasm"),
+    ("wav", "audio/wav"),
+    ("webm", "video/webm"),
+    ("webp", "image/webp"),
+    ("woff", "font/woff"),
+    ("woff2", "font/woff2"),
+    ("xls", "application/vnd.ms-excel"),
+    (
+        "xlsx",
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+    ),
+    ("xml", "application/xml"),
+    ("zip", "application/zip"),
+];
+
+/// Guess the content type of an object from the extension of its key, case insensitive.
+///
+/// Known extensions: `avif`, `bmp`, `css`, `csv`, `doc`, `docx`, `gif`, `gz`, `htm`, `html`,
+/// `ico`, `jpeg`, `jpg`, `js`, `json`, `md`, `mjs`, `mov`, `mp3`, `mp4`, `ogg`, `otf`, `pdf`,
+/// `png`, `ppt`, `pptx`, `svg`, `tar`, `tif`, `tiff`, `ttf`, `txt`, `wasm`, `wav`, `webm`,
+/// `webp`, `woff`, `woff2`, `xls`, `xlsx`, `xml` and `zip`. Returns `None` when the key has no
+/// extension or the extension is unknown.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::guess_content_type;
+///
+/// assert_eq!(guess_content_type("images/photo.JPG"), Some("image/jpeg"));
+/// assert_eq!(guess_content_type("archive.tar.gz"), Some("application/gzip"));
+/// assert_eq!(guess_content_type("v1.2/README"), None);
+/// ```
+pub fn guess_content_type(key: &str) -> Option<&'static str> {
+    let file_name = key.rsplit('/').next().unwrap_or(key);
+    let (_, extension) = file_name.rsplit_once('.')?;
+    let extension = extension.to_ascii_lowercase();
+
+    CONTENT_TYPES
+        .iter()
+        .find(|(ext, _)| *ext == extension)
+        .map(|(_, content_type)| *content_type)
+}
+
+/// Characters of a filename which are not encoded in a RFC 5987 `filename*` parameter.
+const ATTR_CHAR_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'!')
+    .remove(b'#')
+    .remove(b'$')
+    .remove(b'&')
+    .remove(b'+')
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'^')
+    .remove(b'_')
+    .remove(b'`')
+    .remove(b'|')
+    .remove(b'~');
+
+/// Build a `Content-Disposition` value for `filename`, with `disposition` being `attachment` or
+/// `inline`.
+///
+/// Non ASCII filenames get a RFC 5987 `filename*` parameter, and an ASCII `filename` fallback for
+/// old browsers.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::content_disposition;
+///
+/// assert_eq!(content_disposition("attachment", "report.pdf"), r#"attachment; filename="report.pdf""#);
+/// assert_eq!(
+///     content_disposition("attachment", "résumé 2021.pdf"),
+///     r#"attachment; filename="r_sum_ 2021.pdf"; filename*=UTF-8''r%C3%A9sum%C3%A9%202021.pdf"#
+/// );
+/// ```
+pub fn content_disposition(disposition: &str, filename: &str) -> String {
+    let fallback = filename
+        .chars()
+        .map(|c| match c {
+            ' '..='~' if c != '"' && c != '\\' => c,
+            _ => '_',
+        })
+        .collect::<String>();
+
+    if fallback == filename {
+        format!(r#"{}; filename="{}""#, disposition, filename)
+    } else {
+        format!(
+            r#"{}; filename="{}"; filename*=UTF-8''{}"#,
+            disposition,
+            fallback,
+            utf8_percent_encode(filename, ATTR_CHAR_SET),
+        )
+    }
+}
@@ -0,0 +1,801 @@
+use std::convert::TryInto;
+
+use bytes::{Bytes, BytesMut};
+use chrono::{DateTime, Utc};
+use futures_util::stream::{Stream, StreamExt};
+use reqwest::header::ETAG;
+use reqwest::Method;
+use tokio::runtime::Handle;
+
+use crate::error::{Error, S3Error};
+use crate::s3_delete::escape_xml;
+use crate::s3_key::try_key;
+use crate::s3_shutdown::UploadGuard;
+use crate::{
+    CommonPrefix, CompleteMultipartUploadOutput, ListFuture, ListRequest, RequestOptions, S3Key, S3,
+};
+
+/// Smallest part S3 accepts, except for the last part of an upload.
+pub const MIN_PART_SIZE: u64 = 5 * 1024 * 1024;
+/// Largest part S3 accepts.
+pub const MAX_PART_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+/// Maximum number of parts of a multipart upload.
+pub const MAX_PARTS: u64 = 10_000;
+
+const MIB: u64 = 1024 * 1024;
+/// Parts of a stream uploaded before their size doubles, see `PartSizeOptions::stream_part_size`.
+const STREAM_PARTS_PER_SIZE: u64 = 1000;
+
+/// Bounds of the part size picked for a multipart upload, clamped to what S3 accepts.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::{PartSizeOptions, MIN_PART_SIZE};
+///
+/// let options = PartSizeOptions::default();
+/// // Small objects use the smallest parts
+/// assert_eq!(options.part_size(20 * 1024 * 1024).unwrap(), MIN_PART_SIZE);
+/// // 100 GiB needs parts of at least 10.24 MiB to stay under 10000 parts, rounded up to 11 MiB
+/// assert_eq!(options.part_size(100 * 1024 * 1024 * 1024).unwrap(), 11 * 1024 * 1024);
+///
+/// // Bigger parts mean fewer requests
+/// let options = PartSizeOptions::new().min_part_size(64 * 1024 * 1024);
+/// assert_eq!(options.part_size(20 * 1024 * 1024).unwrap(), 64 * 1024 * 1024);
+///
+/// // Parts capped at 8 MiB cannot upload 100 GiB
+/// let options = PartSizeOptions::new().max_part_size(8 * 1024 * 1024);
+/// assert!(options.part_size(100 * 1024 * 1024 * 1024).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartSizeOptions {
+    pub min_part_size: u64,
+    pub max_part_size: u64,
+}
+
+impl Default for PartSizeOptions {
+    fn default() -> Self {
+        Self {
+            min_part_size: MIN_PART_SIZE,
+            max_part_size: MAX_PART_SIZE,
+        }
+    }
+}
+
+impl PartSizeOptions {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn min_part_size(mut self, min_part_size: u64) -> Self {
+        self.min_part_size = min_part_size;
+        self
+    }
+
+    #[inline]
+    pub fn max_part_size(mut self, max_part_size: u64) -> Self {
+        self.max_part_size = max_part_size;
+        self
+    }
+
+    /// Part size for an object of `object_size` bytes: the smallest size, rounded up to a whole
+    /// MiB, keeping the upload under `MAX_PARTS` parts.
+    #[inline]
+    pub fn part_size(&self, object_size: u64) -> Result<u64, Error> {
+        let min = self.min_part_size.max(MIN_PART_SIZE);
+        let max = self.max_part_size.min(MAX_PART_SIZE);
+
+        let needed = div_ceil(div_ceil(object_size, MAX_PARTS), MIB) * MIB;
+        let part_size = needed.max(min);
+        if part_size > max {
+            return Err(Error::ConfigError(format!(
+                "An object of {} bytes needs parts of {} bytes, more than the maximum of {}",
+                object_size, part_size, max
+            )));
+        }
+
+        Ok(part_size)
+    }
+
+    /// Size of the part `part_number` of an upload of unknown size, e.g. from a stream: parts
+    /// start at the minimum size and double every 1000 parts, so 10000 parts of 5 MiB first parts
+    /// upload up to about 5 TiB, the largest object S3 stores.
+    ///
+    /// Example:
+    /// ```rust
+    /// use simple_aws_s3::{PartSizeOptions, MIN_PART_SIZE};
+    ///
+    /// let options = PartSizeOptions::default();
+    /// assert_eq!(options.stream_part_size(1).unwrap(), MIN_PART_SIZE);
+    /// assert_eq!(options.stream_part_size(1001).unwrap(), 2 * MIN_PART_SIZE);
+    /// assert_eq!(options.stream_part_size(10000).unwrap(), 512 * MIN_PART_SIZE);
+    /// assert!(options.stream_part_size(10001).is_err());
+    /// ```
+    #[inline]
+    pub fn stream_part_size(&self, part_number: u64) -> Result<u64, Error> {
+        if part_number == 0 || part_number > MAX_PARTS {
+            return Err(Error::InvalidInput(format!(
+                "Part {} is out of the parts 1 to {} of a multipart upload",
+                part_number, MAX_PARTS
+            )));
+        }
+        let min = self.min_part_size.max(MIN_PART_SIZE);
+        let max = self.max_part_size.min(MAX_PART_SIZE);
+
+        let doublings = (part_number - 1) / STREAM_PARTS_PER_SIZE;
+        Ok(min.saturating_mul(1 << doublings).min(max).max(min))
+    }
+}
+
+/// Number of parts of an object of `object_size` bytes uploaded in parts of `part_size` bytes.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::part_count;
+///
+/// assert_eq!(part_count(12, 5), 3);
+/// assert_eq!(part_count(0, 5), 1);
+/// ```
+#[inline]
+pub fn part_count(object_size: u64, part_size: u64) -> u64 {
+    // An empty object is still uploaded as one (empty) part
+    div_ceil(object_size, part_size.max(1)).max(1)
+}
+
+/// Aborts a multipart upload when dropped before `disarm`, e.g. with a cancelled
+/// `put_object_from_stream` future.
+struct AbortOnDrop {
+    s3: S3,
+    key: S3Key,
+    upload_id: String,
+    upload: Option<UploadGuard>,
+}
+
+impl AbortOnDrop {
+    /// The upload was completed or aborted.
+    #[inline]
+    fn disarm(&mut self) {
+        if let Some(upload) = self.upload.take() {
+            upload.finish();
+        }
+    }
+}
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        let upload = match self.upload.take() {
+            Some(upload) => upload,
+            None => return,
+        };
+        // Without a runtime, the dropped guard leaves the upload to `S3::shutdown` to abort
+        if let Ok(runtime) = Handle::try_current() {
+            let s3 = self.s3.clone();
+            let key = self.key.clone();
+            let upload_id = self.upload_id.clone();
+            runtime.spawn(async move {
+                // Still tracked until aborted, and left to `S3::shutdown` if the abort fails
+                if s3.abort_multipart_upload(&key, &upload_id).await.is_ok() {
+                    upload.finish();
+                }
+            });
+        }
+    }
+}
+
+/// `a / b` rounded up (`u64::div_ceil` needs Rust 1.73).
+#[inline]
+pub(crate) fn div_ceil(a: u64, b: u64) -> u64 {
+    let quotient = a / b;
+    if quotient * b < a {
+        quotient + 1
+    } else {
+        quotient
+    }
+}
+
+/// A part uploaded to a multipart upload, needed to complete it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletedPart {
+    pub part_number: u32,
+    pub etag: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InitiateMultipartUploadResult {
+    upload_id: String,
+}
+
+impl S3 {
+    /// Start a multipart upload of `key` and return its upload id.
+    ///
+    /// `options` holds the headers of the final object, such as its content type, ACL or
+    /// metadata.
+    #[inline]
+    pub async fn create_multipart_upload(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        options: &RequestOptions,
+    ) -> Result<String, Error> {
+        let key = try_key(key)?;
+        let options = self.upload_options(options);
+        let req = self.prepare_request(Method::POST, Some(&key), &[("uploads", "")], &options)?;
+        let res = self.execute(req).await?;
+        let result: InitiateMultipartUploadResult = serde_xml_rs::from_str(&res.text().await?)?;
+        Ok(result.upload_id)
+    }
+
+    /// Upload the part `part_number` (starting at 1) of a multipart upload.
+    #[inline]
+    pub async fn upload_part(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        upload_id: &str,
+        part_number: u32,
+        body: Vec<u8>,
+    ) -> Result<CompletedPart, Error> {
+        let key = try_key(key)?;
+        let part_number_str = part_number.to_string();
+        let query = [
+            ("partNumber", part_number_str.as_str()),
+            ("uploadId", upload_id),
+        ];
+        let req = self.prepare_request_with_body(
+            Method::PUT,
+            Some(&key),
+            &query,
+            &RequestOptions::default(),
+            &[],
+            Some(body),
+        )?;
+        let res = self.execute(req).await?;
+        let etag = match res.headers().get(ETAG).and_then(|v| v.to_str().ok()) {
+            Some(etag) => etag.to_string(),
+            // Parts skipped by dry-run have no ETag
+            None if self.dry_run_log().is_some() => String::new(),
+            None => {
+                return Err(S3Error {
+                    code: "MissingETag".into(),
+                    message: format!("Part {} was uploaded without an ETag", part_number),
+                    ..S3Error::from_status(res.status())
+                }
+                .into())
+            }
+        };
+        Ok(CompletedPart { part_number, etag })
+    }
+
+    /// Assemble the uploaded `parts` into the object.
+    ///
+    /// A completion failing after S3 sent `200 OK` is reported in the body of the response, and
+    /// is an `Error::S3` as well.
+    #[inline]
+    pub async fn complete_multipart_upload(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        upload_id: &str,
+        parts: &[CompletedPart],
+    ) -> Result<CompleteMultipartUploadOutput, Error> {
+        let key = try_key(key)?;
+        let req = self.prepare_request_with_body(
+            Method::POST,
+            Some(&key),
+            &[("uploadId", upload_id)],
+            &RequestOptions::default(),
+            &[],
+            Some(complete_request_body(parts).into_bytes()),
+        )?;
+        let res = self.execute(req).await?;
+        let headers = res.headers().clone();
+        CompleteMultipartUploadOutput::from_parts(&headers, &res.text().await?)
+    }
+
+    /// Abort a multipart upload, freeing the storage of its uploaded parts.
+    #[inline]
+    pub async fn abort_multipart_upload(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        upload_id: &str,
+    ) -> Result<(), Error> {
+        let key = try_key(key)?;
+        let req = self.prepare_request(
+            Method::DELETE,
+            Some(&key),
+            &[("uploadId", upload_id)],
+            &RequestOptions::default(),
+        )?;
+        self.execute(req).await?;
+        Ok(())
+    }
+
+    /// Upload the bytes produced by `stream` as `key`, with a multipart upload.
+    ///
+    /// The stream is buffered into parts which are uploaded as soon as they are full, so data
+    /// produced incrementally never has to be written to a temporary file. Parts grow with the
+    /// stream, see `PartSizeOptions::stream_part_size`. The upload is aborted if a part or the
+    /// completion fails, or if the future is dropped before the end, e.g. cancelled by a timeout.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use futures_util::stream;
+    /// use simple_aws_s3::prelude::bytes::Bytes;
+    /// use simple_aws_s3::{RequestOptions, S3};
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let rows = stream::iter((0..100_000).map(|i| Bytes::from(format!("{},row {}\n", i, i))));
+    /// let options = RequestOptions::new().content_type("text/csv");
+    /// s3.put_object_from_stream("exports/rows.csv", rows, &options).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn put_object_from_stream<S>(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        stream: S,
+        options: &RequestOptions,
+    ) -> Result<(), Error>
+    where
+        S: Stream<Item = Bytes>,
+    {
+        self.put_object_from_stream_with_part_size(key, stream, options, &PartSizeOptions::new())
+            .await
+    }
+
+    /// Same as `put_object_from_stream`, with bounds of the part sizes, e.g. bigger first parts
+    /// for fewer requests.
+    pub async fn put_object_from_stream_with_part_size<S>(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        stream: S,
+        options: &RequestOptions,
+        part_sizes: &PartSizeOptions,
+    ) -> Result<(), Error>
+    where
+        S: Stream<Item = Bytes>,
+    {
+        self.put_object_from_try_stream(key, stream.map(Ok), options, part_sizes)
+            .await
+    }
+
+    /// Same as `put_object_from_stream_with_part_size`, for a stream which may fail, e.g. the
+    /// body of a download. The upload is aborted on the first error of the stream.
+    pub async fn put_object_from_try_stream<S>(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        stream: S,
+        options: &RequestOptions,
+        part_sizes: &PartSizeOptions,
+    ) -> Result<(), Error>
+    where
+        S: Stream<Item = Result<Bytes, Error>>,
+    {
+        let key = &try_key(key)?;
+        let upload_id = self.create_multipart_upload(key, options).await?;
+        let mut upload = AbortOnDrop {
+            s3: self.clone(),
+            key: key.clone(),
+            upload_id: upload_id.clone(),
+            upload: Some(self.in_flight().start_upload(key, &upload_id)),
+        };
+        let res = async {
+            let parts = self
+                .upload_stream_parts(key, &upload_id, stream, part_sizes)
+                .await?;
+            self.complete_multipart_upload(key, &upload_id, &parts)
+                .await
+        }
+        .await;
+        if res.is_err() {
+            // A failed completion leaves the parts stored as well. The upload error matters
+            // more than a failed abort
+            let _ = self.abort_multipart_upload(key, &upload_id).await;
+        }
+        upload.disarm();
+        res.map(|_| ())
+    }
+
+    #[inline]
+    async fn upload_stream_parts<S>(
+        &self,
+        key: &S3Key,
+        upload_id: &str,
+        stream: S,
+        part_sizes: &PartSizeOptions,
+    ) -> Result<Vec<CompletedPart>, Error>
+    where
+        S: Stream<Item = Result<Bytes, Error>>,
+    {
+        futures_util::pin_mut!(stream);
+        let mut parts = vec![];
+        let mut buffer = BytesMut::new();
+
+        while let Some(chunk) = stream.next().await {
+            buffer.extend_from_slice(&chunk?);
+            loop {
+                // Fails before sending a part beyond the last one S3 accepts
+                let part_size = part_sizes.stream_part_size(parts.len() as u64 + 1)? as usize;
+                if buffer.len() < part_size {
+                    break;
+                }
+                let body = buffer.split_to(part_size).to_vec();
+                let part_number = parts.len() as u32 + 1;
+                parts.push(self.upload_part(key, upload_id, part_number, body).await?);
+            }
+        }
+        // The last part may be smaller, and an empty stream is still uploaded as one empty part
+        if !buffer.is_empty() || parts.is_empty() {
+            part_sizes.stream_part_size(parts.len() as u64 + 1)?;
+            let part_number = parts.len() as u32 + 1;
+            parts.push(
+                self.upload_part(key, upload_id, part_number, buffer.to_vec())
+                    .await?,
+            );
+        }
+
+        Ok(parts)
+    }
+}
+
+/// Query params of a ListMultipartUploads request.
+///
+/// Ref: https://docs.aws.amazon.com/AmazonS3/latest/API/API_ListMultipartUploads.html
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListMultipartUploadsOptions {
+    pub prefix: Option<String>,
+    pub delimiter: Option<String>,
+    /// Maximum number of uploads returned in a page, 1000 at most
+    pub max_uploads: Option<u32>,
+    /// Key of the page to list, returned as `next_key_marker` by the previous page
+    pub key_marker: Option<String>,
+    /// Upload of the page to list, returned as `next_upload_id_marker` by the previous page
+    pub upload_id_marker: Option<String>,
+}
+
+impl ListMultipartUploadsOptions {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    #[inline]
+    pub fn delimiter(mut self, delimiter: impl Into<String>) -> Self {
+        self.delimiter = Some(delimiter.into());
+        self
+    }
+
+    #[inline]
+    pub fn max_uploads(mut self, max_uploads: u32) -> Self {
+        self.max_uploads = Some(max_uploads);
+        self
+    }
+
+    #[inline]
+    pub(crate) fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = vec![("uploads", String::new())];
+        if let Some(delimiter) = &self.delimiter {
+            query.push(("delimiter", delimiter.clone()));
+        }
+        if let Some(marker) = &self.key_marker {
+            query.push(("key-marker", marker.clone()));
+        }
+        if let Some(max_uploads) = self.max_uploads {
+            query.push(("max-uploads", max_uploads.to_string()));
+        }
+        if let Some(prefix) = &self.prefix {
+            query.push(("prefix", prefix.clone()));
+        }
+        if let Some(marker) = &self.upload_id_marker {
+            query.push(("upload-id-marker", marker.clone()));
+        }
+        query
+    }
+}
+
+/// A page of a ListMultipartUploads response.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::ListMultipartUploadsOutput;
+///
+/// let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <ListMultipartUploadsResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+///   <Bucket>examplebucket</Bucket>
+///   <KeyMarker></KeyMarker>
+///   <UploadIdMarker></UploadIdMarker>
+///   <NextKeyMarker>videos/b.mp4</NextKeyMarker>
+///   <NextUploadIdMarker>xY7zQ2pN8rT4vW1k</NextUploadIdMarker>
+///   <MaxUploads>2</MaxUploads>
+///   <IsTruncated>true</IsTruncated>
+///   <Upload>
+///     <Key>videos/a.mp4</Key>
+///     <UploadId>VXBsb2FkIElEIGZvciA2aWWpbmcncyBteS1tb3ZpZS5tMnRzIHVwbG9hZA</UploadId>
+///     <StorageClass>STANDARD</StorageClass>
+///     <Initiated>2021-06-01T10:00:00.000Z</Initiated>
+///   </Upload>
+///   <Upload>
+///     <Key>videos/b.mp4</Key>
+///     <UploadId>xY7zQ2pN8rT4vW1k</UploadId>
+///     <StorageClass>STANDARD</StorageClass>
+///     <Initiated>2021-06-02T10:00:00.000Z</Initiated>
+///   </Upload>
+/// </ListMultipartUploadsResult>"#;
+///
+/// let output = ListMultipartUploadsOutput::from_xml(body).unwrap();
+/// assert_eq!(output.uploads.len(), 2);
+/// assert_eq!(output.uploads[1].upload_id, "xY7zQ2pN8rT4vW1k");
+/// assert!(output.is_truncated);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ListMultipartUploadsOutput {
+    pub bucket: String,
+    pub prefix: Option<String>,
+    pub key_marker: Option<String>,
+    pub upload_id_marker: Option<String>,
+    pub next_key_marker: Option<String>,
+    pub next_upload_id_marker: Option<String>,
+    pub is_truncated: bool,
+    #[serde(rename = "Upload", default)]
+    pub uploads: Vec<MultipartUpload>,
+    #[serde(default)]
+    pub common_prefixes: Vec<CommonPrefix>,
+}
+
+impl ListMultipartUploadsOutput {
+    #[inline]
+    pub fn from_xml(body: &str) -> Result<Self, Error> {
+        Ok(serde_xml_rs::from_str(body)?)
+    }
+
+    /// Options of the page after this one, listed with `options`, or `None` for the last page.
+    #[inline]
+    pub fn next_page(
+        &self,
+        options: &ListMultipartUploadsOptions,
+    ) -> Option<ListMultipartUploadsOptions> {
+        if !self.is_truncated {
+            return None;
+        }
+        let mut next = options.clone();
+        next.key_marker = Some(self.next_key_marker.clone()?);
+        next.upload_id_marker = self.next_upload_id_marker.clone();
+        Some(next)
+    }
+}
+
+/// A multipart upload in progress, neither completed nor aborted.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct MultipartUpload {
+    pub key: String,
+    pub upload_id: String,
+    pub initiated: DateTime<Utc>,
+    pub storage_class: Option<String>,
+}
+
+/// Query params of a ListParts request.
+///
+/// Ref: https://docs.aws.amazon.com/AmazonS3/latest/API/API_ListParts.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListPartsOptions {
+    pub key: String,
+    pub upload_id: String,
+    /// Maximum number of parts returned in a page, 1000 at most
+    pub max_parts: Option<u32>,
+    /// Only list parts after this part number, returned as `next_part_number_marker` by the
+    /// previous page
+    pub part_number_marker: Option<u32>,
+}
+
+impl ListPartsOptions {
+    #[inline]
+    pub fn new(key: impl Into<String>, upload_id: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            upload_id: upload_id.into(),
+            max_parts: None,
+            part_number_marker: None,
+        }
+    }
+
+    #[inline]
+    pub fn max_parts(mut self, max_parts: u32) -> Self {
+        self.max_parts = Some(max_parts);
+        self
+    }
+
+    #[inline]
+    pub(crate) fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = vec![];
+        if let Some(max_parts) = self.max_parts {
+            query.push(("max-parts", max_parts.to_string()));
+        }
+        if let Some(marker) = self.part_number_marker {
+            query.push(("part-number-marker", marker.to_string()));
+        }
+        query.push(("uploadId", self.upload_id.clone()));
+        query
+    }
+}
+
+/// A page of a ListParts response.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::ListPartsOutput;
+///
+/// let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <ListPartsResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+///   <Bucket>examplebucket</Bucket>
+///   <Key>videos/a.mp4</Key>
+///   <UploadId>xY7zQ2pN8rT4vW1k</UploadId>
+///   <PartNumberMarker>0</PartNumberMarker>
+///   <NextPartNumberMarker>2</NextPartNumberMarker>
+///   <MaxParts>2</MaxParts>
+///   <IsTruncated>true</IsTruncated>
+///   <Part>
+///     <PartNumber>1</PartNumber>
+///     <LastModified>2021-06-01T10:00:00.000Z</LastModified>
+///     <ETag>"7778aef83f66abc1fa1e8477f296d394"</ETag>
+///     <Size>5242880</Size>
+///   </Part>
+///   <Part>
+///     <PartNumber>2</PartNumber>
+///     <LastModified>2021-06-01T10:01:00.000Z</LastModified>
+///     <ETag>"aaaa18db4cc2f85cedef654fccc4a4x8"</ETag>
+///     <Size>5242880</Size>
+///   </Part>
+///   <StorageClass>STANDARD</StorageClass>
+/// </ListPartsResult>"#;
+///
+/// let output = ListPartsOutput::from_xml(body).unwrap();
+/// assert_eq!(output.parts.len(), 2);
+/// assert_eq!(output.parts[1].part_number, 2);
+/// assert_eq!(output.next_part_number_marker, Some(2));
+/// assert_eq!(output.parts[0].completed().etag, "\"7778aef83f66abc1fa1e8477f296d394\"");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ListPartsOutput {
+    pub key: String,
+    pub upload_id: String,
+    pub next_part_number_marker: Option<u32>,
+    pub is_truncated: bool,
+    #[serde(rename = "Part", default)]
+    pub parts: Vec<Part>,
+}
+
+impl ListPartsOutput {
+    #[inline]
+    pub fn from_xml(body: &str) -> Result<Self, Error> {
+        Ok(serde_xml_rs::from_str(body)?)
+    }
+
+    /// Options of the page after this one, listed with `options`, or `None` for the last page.
+    #[inline]
+    pub fn next_page(&self, options: &ListPartsOptions) -> Option<ListPartsOptions> {
+        if !self.is_truncated {
+            return None;
+        }
+        let mut next = options.clone();
+        next.part_number_marker = Some(self.next_part_number_marker?);
+        Some(next)
+    }
+}
+
+/// A part uploaded to a multipart upload in progress.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Part {
+    pub part_number: u32,
+    pub last_modified: DateTime<Utc>,
+    #[serde(rename = "ETag")]
+    pub etag: String,
+    pub size: u64,
+}
+
+impl Part {
+    /// The part as needed to complete its upload, e.g. when resuming an upload.
+    #[inline]
+    pub fn completed(&self) -> CompletedPart {
+        CompletedPart {
+            part_number: self.part_number,
+            etag: self.etag.clone(),
+        }
+    }
+}
+
+impl S3 {
+    /// List a page of the multipart uploads in progress in the bucket.
+    #[inline]
+    pub async fn list_multipart_uploads(
+        &self,
+        options: &ListMultipartUploadsOptions,
+    ) -> Result<ListMultipartUploadsOutput, Error> {
+        let query = options.query();
+        let query = query
+            .iter()
+            .map(|(name, value)| (*name, value.as_str()))
+            .collect::<Vec<_>>();
+        let req = self.prepare_request(Method::GET, None, &query, &RequestOptions::default())?;
+        let res = self.execute(req).await?;
+        ListMultipartUploadsOutput::from_xml(&res.text().await?)
+    }
+
+    /// List a page of the parts uploaded to a multipart upload.
+    #[inline]
+    pub async fn list_parts(&self, options: &ListPartsOptions) -> Result<ListPartsOutput, Error> {
+        let key = S3Key::lenient(options.key.as_str())?;
+        let query = options.query();
+        let query = query
+            .iter()
+            .map(|(name, value)| (*name, value.as_str()))
+            .collect::<Vec<_>>();
+        let req =
+            self.prepare_request(Method::GET, Some(&key), &query, &RequestOptions::default())?;
+        let res = self.execute(req).await?;
+        ListPartsOutput::from_xml(&res.text().await?)
+    }
+}
+
+impl ListRequest for ListMultipartUploadsOptions {
+    type Page = ListMultipartUploadsOutput;
+    type Item = MultipartUpload;
+
+    #[inline]
+    fn fetch<'a>(&'a self, s3: &'a S3) -> ListFuture<'a, Self::Page> {
+        Box::pin(s3.list_multipart_uploads(self))
+    }
+
+    #[inline]
+    fn next_request(&self, page: &Self::Page) -> Option<Self> {
+        page.next_page(self)
+    }
+
+    #[inline]
+    fn items(page: Self::Page) -> Vec<Self::Item> {
+        page.uploads
+    }
+}
+
+impl ListRequest for ListPartsOptions {
+    type Page = ListPartsOutput;
+    type Item = Part;
+
+    #[inline]
+    fn fetch<'a>(&'a self, s3: &'a S3) -> ListFuture<'a, Self::Page> {
+        Box::pin(s3.list_parts(self))
+    }
+
+    #[inline]
+    fn next_request(&self, page: &Self::Page) -> Option<Self> {
+        page.next_page(self)
+    }
+
+    #[inline]
+    fn items(page: Self::Page) -> Vec<Self::Item> {
+        page.parts
+    }
+}
+
+#[inline]
+fn complete_request_body(parts: &[CompletedPart]) -> String {
+    let mut body = String::from("<CompleteMultipartUpload>");
+    for part in parts {
+        body.push_str(&format!(
+            "<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>",
+            part.part_number,
+            escape_xml(&part.etag)
+        ));
+    }
+    body.push_str("</CompleteMultipartUpload>");
+    body
+}
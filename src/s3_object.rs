@@ -0,0 +1,245 @@
+use std::cmp::min;
+use std::fmt;
+use std::future::Future;
+use std::io::{self, SeekFrom};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use reqwest::StatusCode;
+use tokio::io::{AsyncRead, AsyncSeek, ReadBuf};
+
+use crate::error::{Error, S3Error};
+use crate::{RequestOptions, S3Key, S3};
+
+/// Default number of bytes fetched by each ranged get of an `ObjectReader`.
+pub const DEFAULT_READ_AHEAD: u64 = 1024 * 1024;
+
+/// An object of the bucket of an `S3` client.
+#[derive(Debug, Clone)]
+pub struct S3Object {
+    s3: S3,
+    key: S3Key,
+}
+
+impl S3Object {
+    #[inline]
+    pub(crate) fn new(s3: S3, key: S3Key) -> Self {
+        Self { s3, key }
+    }
+
+    #[inline]
+    pub fn key(&self) -> &S3Key {
+        &self.key
+    }
+
+    /// Open a reader with random access over the object, backed by ranged gets.
+    ///
+    /// The ranged gets are pinned to the ETag of the object when the reader is opened: once
+    /// the object is overwritten, reads fail with the `412 Precondition Failed` of S3 instead of
+    /// mixing bytes of two versions.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::S3;
+    /// use std::io::SeekFrom;
+    /// use tokio::io::{AsyncReadExt, AsyncSeekExt};
+    ///
+    /// # async fn run(s3: S3) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut reader = s3.object("data.zip")?.reader().await?;
+    ///
+    /// // Read the last 22 bytes (end of central directory of a zip)
+    /// let mut eocd = vec![0; 22];
+    /// reader.seek(SeekFrom::End(-22)).await?;
+    /// reader.read_exact(&mut eocd).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn reader(&self) -> Result<ObjectReader, Error> {
+        let head = self
+            .s3
+            .head_object(&self.key)
+            .await?
+            .ok_or_else(not_found)?;
+        let size = head.content_length.unwrap_or(0);
+
+        Ok(ObjectReader {
+            object: self.clone(),
+            size,
+            etag: head.etag,
+            position: 0,
+            buffer: Bytes::new(),
+            buffer_start: 0,
+            read_ahead: DEFAULT_READ_AHEAD,
+            fetch: None,
+        })
+    }
+
+    /// Get the bytes from `start` to `end` (inclusive), of the version with `etag` if any.
+    #[inline]
+    fn fetch_range(&self, start: u64, end: u64, etag: Option<String>) -> FetchFuture {
+        let object = self.clone();
+        Box::pin(async move {
+            let mut options = RequestOptions::new().range(start, Some(end));
+            if let Some(etag) = etag {
+                options = options.if_match(etag);
+            }
+            let res = object
+                .s3
+                .get_object_with_options(&object.key, &options)
+                .await?
+                .ok_or_else(not_found)?;
+            res.bytes().await
+        })
+    }
+}
+
+type FetchFuture = Pin<Box<dyn Future<Output = Result<Bytes, Error>> + Send>>;
+
+/// `AsyncRead` + `AsyncSeek` over an object.
+///
+/// Reads are served from an internal buffer, filled by ranged gets of `read_ahead` bytes.
+pub struct ObjectReader {
+    object: S3Object,
+    size: u64,
+    /// ETag of the object when the reader was opened, required from every ranged get
+    etag: Option<String>,
+    position: u64,
+    buffer: Bytes,
+    buffer_start: u64,
+    read_ahead: u64,
+    /// Start offset and future of the pending ranged get
+    fetch: Option<(u64, FetchFuture)>,
+}
+
+impl ObjectReader {
+    /// Set the number of bytes fetched by each ranged get.
+    #[inline]
+    pub fn with_read_ahead(mut self, read_ahead: u64) -> Self {
+        self.read_ahead = read_ahead.max(1);
+        self
+    }
+
+    /// Size of the object in bytes.
+    #[inline]
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// ETag of the object read.
+    #[inline]
+    pub fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+}
+
+impl fmt::Debug for ObjectReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObjectReader")
+            .field("key", &self.object.key)
+            .field("size", &self.size)
+            .field("etag", &self.etag)
+            .field("position", &self.position)
+            .field("buffer_start", &self.buffer_start)
+            .field("buffer_len", &self.buffer.len())
+            .field("read_ahead", &self.read_ahead)
+            .finish()
+    }
+}
+
+impl AsyncRead for ObjectReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        loop {
+            if this.position >= this.size || buf.remaining() == 0 {
+                return Poll::Ready(Ok(()));
+            }
+
+            let buffer_end = this.buffer_start + this.buffer.len() as u64;
+            if this.position >= this.buffer_start && this.position < buffer_end {
+                let offset = (this.position - this.buffer_start) as usize;
+                let len = min(buf.remaining(), this.buffer.len() - offset);
+                buf.put_slice(&this.buffer[offset..offset + len]);
+                this.position += len as u64;
+                return Poll::Ready(Ok(()));
+            }
+
+            // A pending get started before a seek is useless now
+            if this.fetch.as_ref().map(|(start, _)| *start) != Some(this.position) {
+                let end = min(this.position + this.read_ahead, this.size) - 1;
+                let fetch = this
+                    .object
+                    .fetch_range(this.position, end, this.etag.clone());
+                this.fetch = Some((this.position, fetch));
+            }
+
+            let (start, fetch) = this.fetch.as_mut().unwrap();
+            let bytes = match fetch.as_mut().poll(cx) {
+                Poll::Ready(Ok(bytes)) => bytes,
+                Poll::Ready(Err(e)) => {
+                    this.fetch = None;
+                    // `io::Error::other` needs Rust 1.74
+                    #[allow(clippy::io_other_error)]
+                    return Poll::Ready(Err(io::Error::new(io::ErrorKind::Other, e)));
+                }
+                Poll::Pending => return Poll::Pending,
+            };
+            this.buffer_start = *start;
+            this.buffer = bytes;
+            this.fetch = None;
+
+            if this.buffer.is_empty() {
+                // The object shrank since it was opened
+                return Poll::Ready(Ok(()));
+            }
+        }
+    }
+}
+
+impl AsyncSeek for ObjectReader {
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> io::Result<()> {
+        let this = self.get_mut();
+        let position = match position {
+            SeekFrom::Start(position) => Some(position),
+            SeekFrom::End(offset) => add_offset(this.size, offset),
+            SeekFrom::Current(offset) => add_offset(this.position, offset),
+        };
+        this.position = position.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+
+        Ok(())
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        Poll::Ready(Ok(self.position))
+    }
+}
+
+#[inline]
+fn add_offset(base: u64, offset: i64) -> Option<u64> {
+    if offset >= 0 {
+        base.checked_add(offset as u64)
+    } else {
+        base.checked_sub(offset.unsigned_abs())
+    }
+}
+
+#[inline]
+fn not_found() -> Error {
+    S3Error::from_status(StatusCode::NOT_FOUND).into()
+}
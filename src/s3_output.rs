@@ -0,0 +1,674 @@
+use std::collections::BTreeMap;
+use std::ops::Deref;
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures_util::stream::{self, Stream};
+use reqwest::header::{
+    HeaderMap, ACCEPT_RANGES, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, ETAG,
+    LAST_MODIFIED,
+};
+use reqwest::Response;
+use serde::de::DeserializeOwned;
+
+use crate::error::{Error, S3Error};
+use crate::s3_constant::*;
+use crate::s3_shutdown::RequestGuard;
+
+/// Result of a delete object.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::DeleteObjectOutput;
+/// use simple_aws_s3::prelude::reqwest::header::HeaderMap;
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("x-amz-delete-marker", "true".parse().unwrap());
+/// headers.insert("x-amz-version-id", "3HL4kqtJlcpXroDTDmJ+rmSpXd3dIbrHY".parse().unwrap());
+///
+/// let output = DeleteObjectOutput::from_headers(&headers);
+/// assert!(output.delete_marker);
+/// assert_eq!(output.version_id.as_deref(), Some("3HL4kqtJlcpXroDTDmJ+rmSpXd3dIbrHY"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeleteObjectOutput {
+    /// Whether a delete marker was created, or the deleted version was a delete marker
+    pub delete_marker: bool,
+    /// Version of the delete marker, or the deleted version
+    pub version_id: Option<String>,
+}
+
+impl DeleteObjectOutput {
+    #[inline]
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self {
+            delete_marker: header_str(headers, S3_DELETE_MARKER_KEY) == Some("true"),
+            version_id: header_str(headers, S3_VERSION_ID_KEY).map(String::from),
+        }
+    }
+}
+
+/// Result of a put object.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::PutObjectOutput;
+/// use simple_aws_s3::prelude::reqwest::header::HeaderMap;
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("etag", "\"9b2cf535f27731c974343645a3985328\"".parse().unwrap());
+/// headers.insert("x-amz-checksum-sha256", "LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ=".parse().unwrap());
+///
+/// let output = PutObjectOutput::from_headers(&headers);
+/// assert_eq!(output.etag.as_deref(), Some("\"9b2cf535f27731c974343645a3985328\""));
+/// assert_eq!(output.checksums["sha256"], "LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ=");
+/// assert_eq!(output.version_id, None);
+/// assert_eq!(output.expiration, None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PutObjectOutput {
+    /// ETag of the object, `None` only for an upload skipped by dry-run
+    pub etag: Option<String>,
+    /// Checksums of the object by algorithm, when uploaded with one
+    pub checksums: BTreeMap<String, String>,
+    /// Version of the object, in a versioned bucket
+    pub version_id: Option<String>,
+    /// When a lifecycle rule will expire the object
+    pub expiration: Option<ObjectExpiration>,
+}
+
+impl PutObjectOutput {
+    #[inline]
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let checksums = S3_CHECKSUM_KEYS
+            .iter()
+            .filter_map(|name| {
+                let algorithm = name.trim_start_matches("x-amz-checksum-");
+                Some((
+                    algorithm.to_string(),
+                    header_str(headers, name)?.to_string(),
+                ))
+            })
+            .collect();
+        Self {
+            etag: header_str(headers, ETAG.as_str()).map(String::from),
+            checksums,
+            version_id: header_str(headers, S3_VERSION_ID_KEY).map(String::from),
+            expiration: ObjectExpiration::from_headers(headers),
+        }
+    }
+}
+
+/// Result of a head object: the parsed headers of the object.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::HeadObjectOutput;
+/// use simple_aws_s3::prelude::reqwest::header::HeaderMap;
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("content-length", "4096".parse().unwrap());
+/// headers.insert("content-encoding", "gzip".parse().unwrap());
+/// headers.insert("x-amz-replication-status", "COMPLETED".parse().unwrap());
+/// headers.insert("x-amz-tagging-count", "2".parse().unwrap());
+/// headers.insert("x-amz-meta-owner", "alice".parse().unwrap());
+///
+/// let output = HeadObjectOutput::from_headers(headers);
+/// assert_eq!(output.content_length, Some(4096));
+/// assert_eq!(output.content_encoding.as_deref(), Some("gzip"));
+/// assert_eq!(output.replication_status.as_deref(), Some("COMPLETED"));
+/// assert_eq!(output.tag_count, Some(2));
+/// assert_eq!(output.metadata["owner"], "alice");
+/// ```
+#[derive(Debug, Clone)]
+pub struct HeadObjectOutput {
+    pub content_length: Option<u64>,
+    pub content_type: Option<String>,
+    /// Encoding of the stored body, e.g. `gzip`. Gets return the body as stored, not decoded.
+    pub content_encoding: Option<String>,
+    /// Range of the returned bytes for a ranged get, e.g. `bytes 0-1023/4096`
+    pub content_range: Option<String>,
+    pub accept_ranges: Option<String>,
+    pub etag: Option<String>,
+    pub last_modified: Option<DateTime<Utc>>,
+    pub version_id: Option<String>,
+    pub storage_class: Option<String>,
+    /// When a lifecycle rule will expire the object
+    pub expiration: Option<ObjectExpiration>,
+    /// `PENDING`, `COMPLETED`, `FAILED` or `REPLICA` when the object is replicated
+    pub replication_status: Option<String>,
+    /// Number of tags of the object
+    pub tag_count: Option<u32>,
+    /// Checksums by algorithm (`crc32`, `sha256`...), returned with `RequestOptions::checksum_mode`
+    pub checksums: BTreeMap<String, String>,
+    /// User metadata, without the `x-amz-meta-` prefix
+    pub metadata: BTreeMap<String, String>,
+    headers: HeaderMap,
+}
+
+impl HeadObjectOutput {
+    #[inline]
+    pub fn from_headers(headers: HeaderMap) -> Self {
+        let header = |name| header_str(&headers, name).map(String::from);
+
+        let checksums = S3_CHECKSUM_KEYS
+            .iter()
+            .filter_map(|name| {
+                let algorithm = name.trim_start_matches("x-amz-checksum-");
+                Some((algorithm.to_string(), header(name)?))
+            })
+            .collect();
+        let metadata = headers
+            .iter()
+            .filter_map(|(name, value)| {
+                let name = name.as_str().strip_prefix(S3_METADATA_PREFIX)?;
+                Some((name.to_string(), value.to_str().ok()?.to_string()))
+            })
+            .collect();
+
+        Self {
+            content_length: header_str(&headers, CONTENT_LENGTH.as_str())
+                .and_then(|v| v.parse().ok()),
+            content_type: header(CONTENT_TYPE.as_str()),
+            content_encoding: header(CONTENT_ENCODING.as_str()),
+            content_range: header(CONTENT_RANGE.as_str()),
+            accept_ranges: header(ACCEPT_RANGES.as_str()),
+            etag: header(ETAG.as_str()),
+            last_modified: header_str(&headers, LAST_MODIFIED.as_str())
+                .and_then(|v| DateTime::parse_from_rfc2822(v).ok())
+                .map(|v| v.with_timezone(&Utc)),
+            version_id: header(S3_VERSION_ID_KEY),
+            storage_class: header(S3_STORAGE_CLASS_KEY),
+            expiration: ObjectExpiration::from_headers(&headers),
+            replication_status: header(S3_REPLICATION_STATUS_KEY),
+            tag_count: header_str(&headers, S3_TAGGING_COUNT_KEY).and_then(|v| v.parse().ok()),
+            checksums,
+            metadata,
+            headers,
+        }
+    }
+
+    /// All the headers of the response.
+    #[inline]
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+}
+
+/// Result of a get object: the parsed headers of the object (see `HeadObjectOutput`), and its
+/// body.
+///
+/// Example:
+/// ```rust,no_run
+/// use simple_aws_s3::S3;
+///
+/// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+/// if let Some(output) = s3.get_object("report.csv").await? {
+///     println!("{:?} bytes, etag {:?}", output.content_length, output.etag);
+///     let body = output.bytes().await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct GetObjectOutput {
+    head: HeadObjectOutput,
+    response: Response,
+}
+
+impl GetObjectOutput {
+    #[inline]
+    pub fn from_response(response: Response) -> Self {
+        Self {
+            head: HeadObjectOutput::from_headers(response.headers().clone()),
+            response,
+        }
+    }
+
+    /// Read the next chunk of the body, `None` at its end.
+    #[inline]
+    pub async fn chunk(&mut self) -> Result<Option<Bytes>, Error> {
+        Ok(self.response.chunk().await?)
+    }
+
+    #[inline]
+    pub async fn bytes(mut self) -> Result<Bytes, Error> {
+        // `Response::bytes` drops the extensions, holding the request guard, before the body
+        let _request = self.response.extensions_mut().remove::<RequestGuard>();
+        Ok(self.response.bytes().await?)
+    }
+
+    #[inline]
+    pub async fn text(mut self) -> Result<String, Error> {
+        let _request = self.response.extensions_mut().remove::<RequestGuard>();
+        Ok(self.response.text().await?)
+    }
+
+    /// Stream the chunks of the body, ending after its first error.
+    pub fn into_stream(self) -> impl Stream<Item = Result<Bytes, Error>> + Send + 'static {
+        stream::unfold(Some(self), |object| async move {
+            let mut object = object?;
+            match object.chunk().await {
+                Ok(Some(chunk)) => Some((Ok(chunk), Some(object))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e), None)),
+            }
+        })
+    }
+
+    /// The underlying response, e.g. to stream its body with `bytes_limited`.
+    #[inline]
+    pub fn into_response(self) -> Response {
+        self.response
+    }
+}
+
+impl Deref for GetObjectOutput {
+    type Target = HeadObjectOutput;
+
+    fn deref(&self) -> &HeadObjectOutput {
+        &self.head
+    }
+}
+
+/// Expiration of an object by a lifecycle rule, parsed from the `x-amz-expiration` header of
+/// head, get, put and complete multipart upload responses.
+///
+/// Example:
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use simple_aws_s3::ObjectExpiration;
+///
+/// let header = r#"expiry-date="Fri, 21 Dec 2012 00:00:00 GMT", rule-id="picture-deletion-rule""#;
+/// let expiration = ObjectExpiration::parse(header).unwrap();
+/// assert_eq!(expiration.expiry_date, Utc.ymd(2012, 12, 21).and_hms(0, 0, 0));
+/// assert_eq!(expiration.rule_id, "picture-deletion-rule");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectExpiration {
+    pub expiry_date: DateTime<Utc>,
+    /// Id of the lifecycle rule expiring the object
+    pub rule_id: String,
+}
+
+impl ObjectExpiration {
+    /// Parse an `x-amz-expiration` header value, `None` if it is malformed.
+    #[inline]
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut expiry_date = None;
+        let mut rule_id = None;
+        let mut rest = value;
+        while let Some((name, after)) = rest.split_once("=\"") {
+            let (field, after) = after.split_once('"')?;
+            match name.trim_matches(|c: char| c == ',' || c.is_whitespace()) {
+                "expiry-date" => expiry_date = Some(field),
+                "rule-id" => rule_id = Some(field),
+                _ => {}
+            }
+            rest = after;
+        }
+
+        Some(Self {
+            expiry_date: DateTime::parse_from_rfc2822(expiry_date?)
+                .ok()?
+                .with_timezone(&Utc),
+            rule_id: rule_id?.to_string(),
+        })
+    }
+
+    #[inline]
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        Self::parse(header_str(headers, S3_EXPIRATION_KEY)?)
+    }
+}
+
+#[inline]
+pub(crate) fn header_str<'h>(headers: &'h HeaderMap, name: &str) -> Option<&'h str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+/// Result of a batch delete.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::DeleteObjectsOutput;
+///
+/// let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <DeleteResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+///   <Deleted>
+///     <Key>logs/2021-01-01.gz</Key>
+///   </Deleted>
+///   <Error>
+///     <Key>logs/2021-01-02.gz</Key>
+///     <Code>AccessDenied</Code>
+///     <Message>Access Denied</Message>
+///   </Error>
+///   <Deleted>
+///     <Key>logs/2021-01-03.gz</Key>
+///     <DeleteMarker>true</DeleteMarker>
+///     <DeleteMarkerVersionId>3HL4kqtJlcpXroDTDmJ+rmSpXd3dIbrHY</DeleteMarkerVersionId>
+///   </Deleted>
+/// </DeleteResult>"#;
+///
+/// let output = DeleteObjectsOutput::from_xml(body).unwrap();
+/// assert_eq!(output.deleted.len(), 2);
+/// assert!(output.deleted[1].delete_marker);
+/// // Only the failed keys have to be retried
+/// assert_eq!(output.failed_keys(), vec!["logs/2021-01-02.gz"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeleteObjectsOutput {
+    /// Objects which were deleted
+    pub deleted: Vec<DeletedObject>,
+    /// Keys which could not be deleted
+    pub errors: Vec<DeleteObjectError>,
+}
+
+impl DeleteObjectsOutput {
+    /// Parse a batch delete response.
+    ///
+    /// A quiet response only holds the errors, the deleted objects have to be derived from the
+    /// requested keys.
+    #[inline]
+    pub fn from_xml(body: &str) -> Result<Self, Error> {
+        let result: DeleteResult = serde_xml_rs::from_str(body)?;
+        let mut output = Self::default();
+        for entry in result.entries {
+            match entry {
+                DeleteResultEntry::Deleted(deleted) => output.deleted.push(deleted),
+                DeleteResultEntry::Error(error) => output.errors.push(error),
+            }
+        }
+        Ok(output)
+    }
+
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Keys which could not be deleted, to retry them.
+    #[inline]
+    pub fn failed_keys(&self) -> Vec<&str> {
+        self.errors.iter().map(|error| error.key.as_str()).collect()
+    }
+
+    #[inline]
+    pub(crate) fn extend(&mut self, other: Self) {
+        self.deleted.extend(other.deleted);
+        self.errors.extend(other.errors);
+    }
+}
+
+/// An object deleted by a batch delete.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DeletedObject {
+    pub key: String,
+    /// Version which was deleted, when a version was requested
+    #[serde(default)]
+    pub version_id: Option<String>,
+    /// Whether a delete marker was created, or the deleted version was a delete marker
+    #[serde(default)]
+    pub delete_marker: bool,
+    /// Version of the created delete marker
+    #[serde(default)]
+    pub delete_marker_version_id: Option<String>,
+}
+
+impl DeletedObject {
+    #[inline]
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A key S3 refused to delete in a batch delete.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::DeleteObjectError;
+///
+/// let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <DeleteResult>
+///   <Error>
+///     <Key>logs/2021-01-01.gz</Key>
+///     <Code>AccessDenied</Code>
+///     <Message>Access Denied</Message>
+///   </Error>
+/// </DeleteResult>"#;
+///
+/// let errors = DeleteObjectError::from_xml(body).unwrap();
+/// assert_eq!(errors[0].key, "logs/2021-01-01.gz");
+/// assert_eq!(errors[0].code, "AccessDenied");
+///
+/// let body = r#"<DeleteResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/"></DeleteResult>"#;
+/// assert!(DeleteObjectError::from_xml(body).unwrap().is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DeleteObjectError {
+    pub key: String,
+    pub code: String,
+    #[serde(default)]
+    pub message: String,
+}
+
+impl DeleteObjectError {
+    /// Parse the errors of a batch delete response.
+    #[inline]
+    pub fn from_xml(body: &str) -> Result<Vec<Self>, Error> {
+        Ok(DeleteObjectsOutput::from_xml(body)?.errors)
+    }
+}
+
+// Deleted and Error elements can be interleaved, so they are read as a single sequence
+#[derive(Debug, Deserialize)]
+struct DeleteResult {
+    #[serde(rename = "$value", default)]
+    entries: Vec<DeleteResultEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+enum DeleteResultEntry {
+    Deleted(DeletedObject),
+    Error(DeleteObjectError),
+}
+
+/// Result of a copy object.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::CopyObjectOutput;
+/// use simple_aws_s3::error::Error;
+/// use simple_aws_s3::prelude::reqwest::header::HeaderMap;
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("x-amz-version-id", "QUpfdndhfd8438MNFDN93jdnJFkdmqnh893".parse().unwrap());
+/// let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <CopyObjectResult>
+///   <LastModified>2021-06-01T10:00:00.000Z</LastModified>
+///   <ETag>"9b2cf535f27731c974343645a3985328"</ETag>
+///   <ChecksumSHA256>LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ=</ChecksumSHA256>
+/// </CopyObjectResult>"#;
+///
+/// let output = CopyObjectOutput::from_parts(&headers, body).unwrap();
+/// assert_eq!(output.etag.as_deref(), Some("\"9b2cf535f27731c974343645a3985328\""));
+/// assert_eq!(output.checksums["sha256"], "LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ=");
+/// assert_eq!(output.version_id.as_deref(), Some("QUpfdndhfd8438MNFDN93jdnJFkdmqnh893"));
+///
+/// // The copy failed after S3 sent `200 OK`
+/// let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <Error><Code>InternalError</Code><Message>We encountered an internal error. Please try again.</Message></Error>"#;
+/// assert!(matches!(CopyObjectOutput::from_parts(&headers, body), Err(Error::S3(_))));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CopyObjectOutput {
+    /// ETag of the copy, `None` only for a copy skipped by dry-run
+    pub etag: Option<String>,
+    pub last_modified: Option<DateTime<Utc>>,
+    /// Checksums of the copy by algorithm (`crc32`, `sha256`...), when the source has some
+    pub checksums: BTreeMap<String, String>,
+    /// Version of the copy, in a versioned bucket
+    pub version_id: Option<String>,
+    /// Version of the source which was copied
+    pub copy_source_version_id: Option<String>,
+}
+
+impl CopyObjectOutput {
+    /// Parse the headers and body of a CopyObject response. An error in the body is
+    /// `Error::S3`, see `S3Error::from_ok_body`.
+    #[inline]
+    pub fn from_parts(headers: &HeaderMap, body: &str) -> Result<Self, Error> {
+        let mut output = Self {
+            version_id: header_str(headers, S3_VERSION_ID_KEY).map(String::from),
+            copy_source_version_id: header_str(headers, S3_COPY_SOURCE_VERSION_ID_KEY)
+                .map(String::from),
+            ..Self::default()
+        };
+        if let Some(result) = parse_ok_body::<CopyObjectResultXml>(body)? {
+            output.etag = result.etag;
+            output.last_modified = result.last_modified;
+            output.checksums = checksums_of(
+                result.checksum_crc32,
+                result.checksum_crc32c,
+                result.checksum_sha1,
+                result.checksum_sha256,
+            );
+        }
+        Ok(output)
+    }
+}
+
+/// Result of a complete multipart upload.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::CompleteMultipartUploadOutput;
+/// use simple_aws_s3::prelude::reqwest::header::HeaderMap;
+///
+/// let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <CompleteMultipartUploadResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+///   <Location>https://examplebucket.s3.amazonaws.com/videos/a.mp4</Location>
+///   <Bucket>examplebucket</Bucket>
+///   <Key>videos/a.mp4</Key>
+///   <ETag>"3858f62230ac3c915f300c664312c11f-9"</ETag>
+/// </CompleteMultipartUploadResult>"#;
+///
+/// let output = CompleteMultipartUploadOutput::from_parts(&HeaderMap::new(), body).unwrap();
+/// assert_eq!(output.key.as_deref(), Some("videos/a.mp4"));
+/// assert_eq!(output.etag.as_deref(), Some("\"3858f62230ac3c915f300c664312c11f-9\""));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompleteMultipartUploadOutput {
+    pub location: Option<String>,
+    pub bucket: Option<String>,
+    pub key: Option<String>,
+    /// ETag of the object, `None` only for an upload skipped by dry-run
+    pub etag: Option<String>,
+    /// Checksums of the object by algorithm, checksums of the checksums of its parts
+    pub checksums: BTreeMap<String, String>,
+    /// Version of the object, in a versioned bucket
+    pub version_id: Option<String>,
+    /// When a lifecycle rule will expire the object
+    pub expiration: Option<ObjectExpiration>,
+}
+
+impl CompleteMultipartUploadOutput {
+    /// Parse the headers and body of a CompleteMultipartUpload response. An error in the body
+    /// is `Error::S3`, see `S3Error::from_ok_body`.
+    #[inline]
+    pub fn from_parts(headers: &HeaderMap, body: &str) -> Result<Self, Error> {
+        let mut output = Self {
+            version_id: header_str(headers, S3_VERSION_ID_KEY).map(String::from),
+            expiration: ObjectExpiration::from_headers(headers),
+            ..Self::default()
+        };
+        if let Some(result) = parse_ok_body::<CompleteMultipartUploadResultXml>(body)? {
+            output.location = result.location;
+            output.bucket = result.bucket;
+            output.key = result.key;
+            output.etag = result.etag;
+            output.checksums = checksums_of(
+                result.checksum_crc32,
+                result.checksum_crc32c,
+                result.checksum_sha1,
+                result.checksum_sha256,
+            );
+        }
+        Ok(output)
+    }
+}
+
+/// Parse the body of a `200 OK` response, `None` if empty.
+#[inline]
+fn parse_ok_body<T: DeserializeOwned>(body: &str) -> Result<Option<T>, Error> {
+    if body.trim().is_empty() {
+        return Ok(None);
+    }
+    if let Some(e) = S3Error::from_ok_body(body) {
+        return Err(e.into());
+    }
+    Ok(Some(serde_xml_rs::from_str(body)?))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CopyObjectResultXml {
+    #[serde(rename = "ETag", default)]
+    etag: Option<String>,
+    #[serde(default)]
+    last_modified: Option<DateTime<Utc>>,
+    #[serde(rename = "ChecksumCRC32", default)]
+    checksum_crc32: Option<String>,
+    #[serde(rename = "ChecksumCRC32C", default)]
+    checksum_crc32c: Option<String>,
+    #[serde(rename = "ChecksumSHA1", default)]
+    checksum_sha1: Option<String>,
+    #[serde(rename = "ChecksumSHA256", default)]
+    checksum_sha256: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CompleteMultipartUploadResultXml {
+    #[serde(default)]
+    location: Option<String>,
+    #[serde(default)]
+    bucket: Option<String>,
+    #[serde(default)]
+    key: Option<String>,
+    #[serde(rename = "ETag", default)]
+    etag: Option<String>,
+    #[serde(rename = "ChecksumCRC32", default)]
+    checksum_crc32: Option<String>,
+    #[serde(rename = "ChecksumCRC32C", default)]
+    checksum_crc32c: Option<String>,
+    #[serde(rename = "ChecksumSHA1", default)]
+    checksum_sha1: Option<String>,
+    #[serde(rename = "ChecksumSHA256", default)]
+    checksum_sha256: Option<String>,
+}
+
+/// Checksums by algorithm, from the checksum elements of a response.
+#[inline]
+fn checksums_of(
+    crc32: Option<String>,
+    crc32c: Option<String>,
+    sha1: Option<String>,
+    sha256: Option<String>,
+) -> BTreeMap<String, String> {
+    let checksums = vec![
+        ("crc32", crc32),
+        ("crc32c", crc32c),
+        ("sha1", sha1),
+        ("sha256", sha256),
+    ];
+    checksums
+        .into_iter()
+        .filter_map(|(algorithm, checksum)| Some((algorithm.to_string(), checksum?)))
+        .collect()
+}
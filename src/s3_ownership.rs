@@ -0,0 +1,154 @@
+use std::fmt;
+use std::str::FromStr;
+
+use reqwest::Method;
+
+use crate::error::Error;
+use crate::{RequestOptions, S3};
+
+/// Who owns the objects uploaded to a bucket, and whether ACLs are enabled.
+///
+/// Ref: https://docs.aws.amazon.com/AmazonS3/latest/userguide/about-object-ownership.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ObjectOwnership {
+    /// ACLs are disabled, the bucket owner owns every object. The default of new buckets.
+    BucketOwnerEnforced,
+    /// The bucket owner owns the objects uploaded with the `bucket-owner-full-control` ACL
+    BucketOwnerPreferred,
+    /// The uploading account owns the object
+    ObjectWriter,
+}
+
+impl ObjectOwnership {
+    #[inline]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::BucketOwnerEnforced => "BucketOwnerEnforced",
+            Self::BucketOwnerPreferred => "BucketOwnerPreferred",
+            Self::ObjectWriter => "ObjectWriter",
+        }
+    }
+
+    /// Parse a GetBucketOwnershipControls response.
+    ///
+    /// Example:
+    /// ```rust
+    /// use simple_aws_s3::ObjectOwnership;
+    ///
+    /// let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+    /// <OwnershipControls xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+    ///   <Rule>
+    ///     <ObjectOwnership>BucketOwnerEnforced</ObjectOwnership>
+    ///   </Rule>
+    /// </OwnershipControls>"#;
+    ///
+    /// let ownership = ObjectOwnership::from_xml(body).unwrap();
+    /// assert_eq!(ownership, ObjectOwnership::BucketOwnerEnforced);
+    /// assert!(ownership.to_xml().contains("<ObjectOwnership>BucketOwnerEnforced</ObjectOwnership>"));
+    /// ```
+    #[inline]
+    pub fn from_xml(body: &str) -> Result<Self, Error> {
+        let controls: OwnershipControls = serde_xml_rs::from_str(body)?;
+        controls.rule.object_ownership.parse()
+    }
+
+    #[inline]
+    pub fn to_xml(&self) -> String {
+        format!(
+            r#"<OwnershipControls xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Rule><ObjectOwnership>{}</ObjectOwnership></Rule></OwnershipControls>"#,
+            self.as_str()
+        )
+    }
+}
+
+impl fmt::Display for ObjectOwnership {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for ObjectOwnership {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "BucketOwnerEnforced" => Ok(Self::BucketOwnerEnforced),
+            "BucketOwnerPreferred" => Ok(Self::BucketOwnerPreferred),
+            "ObjectWriter" => Ok(Self::ObjectWriter),
+            _ => Err(Error::InvalidInput(format!(
+                "Unknown object ownership {:?}",
+                s
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct OwnershipControls {
+    rule: OwnershipControlsRule,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct OwnershipControlsRule {
+    object_ownership: String,
+}
+
+impl S3 {
+    /// Get the object ownership of the bucket, `None` if it has no ownership controls.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::{ObjectOwnership, S3};
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// if s3.get_bucket_ownership_controls().await? != Some(ObjectOwnership::BucketOwnerEnforced) {
+    ///     s3.put_bucket_ownership_controls(ObjectOwnership::BucketOwnerEnforced).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn get_bucket_ownership_controls(&self) -> Result<Option<ObjectOwnership>, Error> {
+        let req = self.prepare_request(
+            Method::GET,
+            None,
+            &[("ownershipControls", "")],
+            &RequestOptions::default(),
+        )?;
+        match self.execute(req).await {
+            Ok(res) => Ok(Some(ObjectOwnership::from_xml(&res.text().await?)?)),
+            // OwnershipControlsNotFoundError
+            Err(Error::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    #[inline]
+    pub async fn put_bucket_ownership_controls(
+        &self,
+        ownership: ObjectOwnership,
+    ) -> Result<(), Error> {
+        let req = self.prepare_xml_request(
+            Method::PUT,
+            None,
+            &[("ownershipControls", "")],
+            ownership.to_xml(),
+        )?;
+        self.execute(req).await?;
+        Ok(())
+    }
+
+    #[inline]
+    pub async fn delete_bucket_ownership_controls(&self) -> Result<(), Error> {
+        let req = self.prepare_request(
+            Method::DELETE,
+            None,
+            &[("ownershipControls", "")],
+            &RequestOptions::default(),
+        )?;
+        self.execute(req).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use futures_util::stream::{self, Stream, TryStreamExt};
+
+use crate::error::Error;
+use crate::S3;
+
+/// Future returned by `ListRequest::fetch`.
+pub type ListFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, Error>> + Send + 'a>>;
+
+/// Request of a listing API returning its results by pages, e.g. `ListObjectsOptions`.
+pub trait ListRequest: Clone + Send + Sync + 'static {
+    type Page: Send + 'static;
+    type Item: Send + 'static;
+
+    /// List the page of this request.
+    fn fetch<'a>(&'a self, s3: &'a S3) -> ListFuture<'a, Self::Page>;
+
+    /// Request of the page after `page`, `None` for the last page.
+    fn next_request(&self, page: &Self::Page) -> Option<Self>;
+
+    /// Items of `page`.
+    fn items(page: Self::Page) -> Vec<Self::Item>;
+}
+
+/// Pages of a listing, fetched one after the other. Every listing API of the crate is paginated
+/// the same way: `next_page`, `pages_stream`, `items_stream` and `collect_all`.
+///
+/// Example:
+/// ```rust,no_run
+/// use futures_util::TryStreamExt;
+/// use simple_aws_s3::{ListObjectsOptions, S3};
+///
+/// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+/// // Page by page
+/// let mut paginator = s3.paginator(ListObjectsOptions::new().prefix("logs/"));
+/// while let Some(page) = paginator.next_page().await {
+///     println!("{} objects", page?.contents.len());
+/// }
+///
+/// // Item by item
+/// let objects = s3.paginator(ListObjectsOptions::new().prefix("logs/")).items_stream();
+/// futures_util::pin_mut!(objects);
+/// while let Some(object) = objects.try_next().await? {
+///     println!("{}", object.key);
+/// }
+///
+/// // The first 5000 items at most
+/// let objects = s3.paginator(ListObjectsOptions::new()).collect_all(Some(5000)).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Paginator<T> {
+    s3: S3,
+    next: Option<T>,
+}
+
+impl<T: ListRequest> Paginator<T> {
+    #[inline]
+    pub fn new(s3: S3, request: T) -> Self {
+        Self {
+            s3,
+            next: Some(request),
+        }
+    }
+
+    /// Request of the next page, `None` once the last page was listed.
+    #[inline]
+    pub fn next_request(&self) -> Option<&T> {
+        self.next.as_ref()
+    }
+
+    /// List the next page, `None` once the last page was listed. A failed page can be listed
+    /// again by calling `next_page` again.
+    pub async fn next_page(&mut self) -> Option<Result<T::Page, Error>> {
+        let request = self.next.as_ref()?;
+        match request.fetch(&self.s3).await {
+            Ok(page) => {
+                self.next = request.next_request(&page);
+                Some(Ok(page))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+
+    /// Stream the pages, listing them as they are consumed. The stream ends after an error.
+    pub fn pages_stream(self) -> impl Stream<Item = Result<T::Page, Error>> + Send + 'static {
+        stream::unfold(Some(self), |paginator| async move {
+            let mut paginator = paginator?;
+            match paginator.next_page().await? {
+                Ok(page) => Some((Ok(page), Some(paginator))),
+                Err(e) => Some((Err(e), None)),
+            }
+        })
+    }
+
+    /// Stream the items of every page, listing the pages as they are consumed.
+    pub fn items_stream(self) -> impl Stream<Item = Result<T::Item, Error>> + Send + 'static {
+        self.pages_stream()
+            .map_ok(|page| stream::iter(T::items(page).into_iter().map(Ok)))
+            .try_flatten()
+    }
+
+    /// Collect the items of every page, up to `limit` items. Pages are no longer listed once
+    /// the limit is reached.
+    pub async fn collect_all(mut self, limit: Option<usize>) -> Result<Vec<T::Item>, Error> {
+        let limit = limit.unwrap_or(usize::MAX);
+        let mut items = vec![];
+        while items.len() < limit {
+            match self.next_page().await {
+                Some(page) => items.extend(T::items(page?)),
+                None => break,
+            }
+        }
+        items.truncate(limit);
+        Ok(items)
+    }
+}
+
+impl S3 {
+    /// Paginate the listing `request`, e.g. `ListObjectsOptions`, `ListVersionsOptions`,
+    /// `ListMultipartUploadsOptions` or `ListPartsOptions`.
+    #[inline]
+    pub fn paginator<T: ListRequest>(&self, request: T) -> Paginator<T> {
+        Paginator::new(self.clone(), request)
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+
+use futures_util::stream::{self, FuturesUnordered, Stream, StreamExt};
+
+use crate::error::Error;
+use crate::{ListObjectsOptions, ListObjectsOutput, ObjectSummary, S3};
+
+type PageFuture =
+    Pin<Box<dyn Future<Output = (ListObjectsOptions, Result<ListObjectsOutput, Error>)> + Send>>;
+
+struct ParallelListState {
+    s3: S3,
+    /// Shards not listed yet
+    shards: VecDeque<ListObjectsOptions>,
+    /// Next page of the shards being listed
+    pages: FuturesUnordered<PageFuture>,
+    objects: VecDeque<ObjectSummary>,
+    concurrency: usize,
+}
+
+impl ParallelListState {
+    #[inline]
+    fn list(&mut self, options: ListObjectsOptions) {
+        let s3 = self.s3.clone();
+        self.pages.push(Box::pin(async move {
+            let page = s3.list_objects_v2(&options).await;
+            (options, page)
+        }));
+    }
+
+    /// Start listing shards until `concurrency` of them are listed at once.
+    #[inline]
+    fn fill(&mut self) {
+        while self.pages.len() < self.concurrency {
+            match self.shards.pop_front() {
+                Some(shard) => self.list(shard),
+                None => return,
+            }
+        }
+    }
+}
+
+/// Helpers to list huge buckets by listing several parts of the keyspace at once.
+impl S3 {
+    /// Split the keys under `prefix` into shards listable in parallel: one shard by direct sub
+    /// folder (common prefix up to the next `/`), and one for the objects directly under
+    /// `prefix`.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::S3;
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let shards = s3.list_shards("logs/").await?;
+    /// for shard in &shards {
+    ///     println!("{:?}", shard.prefix);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn list_shards(&self, prefix: &str) -> Result<Vec<ListObjectsOptions>, Error> {
+        let root = ListObjectsOptions::new().prefix(prefix).delimiter("/");
+        let mut shards = vec![root.clone()];
+        let mut options = Some(root);
+
+        while let Some(current) = options {
+            let page = self.list_objects_v2(&current).await?;
+            shards.extend(
+                page.common_prefixes
+                    .iter()
+                    .map(|p| ListObjectsOptions::new().prefix(p.prefix.as_str())),
+            );
+            options = page.next_page(&current);
+        }
+        Ok(shards)
+    }
+
+    /// Stream the objects of every shard, listing up to `concurrency` shards at once. Objects
+    /// are yielded as their pages arrive, in no particular order across shards.
+    ///
+    /// Shards must not overlap, otherwise their common objects are yielded more than once. A
+    /// failed page yields its error and ends the listing of its shard only.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use futures_util::TryStreamExt;
+    /// use simple_aws_s3::{ListObjectsOptions, S3};
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// // Keys are spread by their first hex digit
+    /// let shards = "0123456789abcdef"
+    ///     .chars()
+    ///     .map(|c| ListObjectsOptions::new().prefix(format!("blobs/{}", c)))
+    ///     .collect();
+    /// let count = s3
+    ///     .list_objects_parallel(shards, 16)
+    ///     .try_fold(0, |count, _| async move { Ok(count + 1) })
+    ///     .await?;
+    ///
+    /// // Or shard by the sub folders of a prefix
+    /// let shards = s3.list_shards("logs/").await?;
+    /// let objects = s3.list_objects_parallel(shards, 8);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_objects_parallel(
+        &self,
+        shards: Vec<ListObjectsOptions>,
+        concurrency: usize,
+    ) -> impl Stream<Item = Result<ObjectSummary, Error>> + Send + 'static {
+        let state = ParallelListState {
+            s3: self.clone(),
+            shards: shards.into(),
+            pages: FuturesUnordered::new(),
+            objects: VecDeque::new(),
+            concurrency: concurrency.max(1),
+        };
+
+        stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(object) = state.objects.pop_front() {
+                    return Some((Ok(object), state));
+                }
+                state.fill();
+                let (options, page) = state.pages.next().await?;
+                match page {
+                    Ok(page) => {
+                        if let Some(next) = page.next_page(&options) {
+                            state.list(next);
+                        }
+                        state.objects.extend(page.contents);
+                    }
+                    Err(e) => return Some((Err(e), state)),
+                }
+            }
+        })
+    }
+}
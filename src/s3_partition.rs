@@ -0,0 +1,148 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::Error;
+
+/// AWS partition, a group of regions sharing their endpoints domain and ARN prefix.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::Partition;
+///
+/// assert_eq!(Partition::from_region("cn-north-1"), Partition::AwsCn);
+/// assert_eq!(Partition::from_region("us-gov-west-1"), Partition::AwsUsGov);
+/// assert_eq!(Partition::from_region("eu-west-3"), Partition::Aws);
+///
+/// assert_eq!(Partition::default_endpoint("cn-north-1"), "s3.cn-north-1.amazonaws.com.cn");
+/// assert_eq!(Partition::default_endpoint("us-east-1"), "s3.us-east-1.amazonaws.com");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Partition {
+    /// Standard regions, `arn:aws:...`
+    Aws,
+    /// China regions, `arn:aws-cn:...`
+    AwsCn,
+    /// GovCloud (US) regions, `arn:aws-us-gov:...`
+    AwsUsGov,
+}
+
+impl Partition {
+    #[inline]
+    pub fn from_region(region: &str) -> Self {
+        if region.starts_with("cn-") {
+            Self::AwsCn
+        } else if region.starts_with("us-gov-") {
+            Self::AwsUsGov
+        } else {
+            Self::Aws
+        }
+    }
+
+    /// Name of the partition in ARNs.
+    #[inline]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Aws => "aws",
+            Self::AwsCn => "aws-cn",
+            Self::AwsUsGov => "aws-us-gov",
+        }
+    }
+
+    /// Domain of the endpoints of the partition.
+    #[inline]
+    pub fn dns_suffix(&self) -> &'static str {
+        match self {
+            Self::Aws | Self::AwsUsGov => "amazonaws.com",
+            Self::AwsCn => "amazonaws.com.cn",
+        }
+    }
+
+    /// S3 endpoint of `region`, as expected by `S3::new`.
+    #[inline]
+    pub fn default_endpoint(region: &str) -> String {
+        format!(
+            "s3.{region}.{suffix}",
+            region = region,
+            suffix = Self::from_region(region).dns_suffix(),
+        )
+    }
+}
+
+impl fmt::Display for Partition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Partition {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "aws" => Ok(Self::Aws),
+            "aws-cn" => Ok(Self::AwsCn),
+            "aws-us-gov" => Ok(Self::AwsUsGov),
+            _ => Err(Error::ConfigError(format!("Unknown partition {:?}", s))),
+        }
+    }
+}
+
+/// Amazon Resource Name, `arn:{partition}:{service}:{region}:{account_id}:{resource}`.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::{Arn, Partition};
+///
+/// let arn: Arn = "arn:aws-cn:s3:::examplebucket".parse().unwrap();
+/// assert_eq!(arn.partition, Partition::AwsCn);
+/// assert_eq!(arn.service, "s3");
+/// assert_eq!(arn.region, "");
+/// assert_eq!(arn.resource, "examplebucket");
+/// assert_eq!(arn.to_string(), "arn:aws-cn:s3:::examplebucket");
+///
+/// assert!("arn:aws-eu:s3:::examplebucket".parse::<Arn>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Arn {
+    pub partition: Partition,
+    pub service: String,
+    pub region: String,
+    pub account_id: String,
+    pub resource: String,
+}
+
+impl FromStr for Arn {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::ConfigError(format!("Invalid ARN {:?}", s));
+        let mut parts = s.splitn(6, ':');
+        if parts.next() != Some("arn") {
+            return Err(invalid());
+        }
+        let mut next = || parts.next().ok_or_else(invalid);
+        let partition = next()?.parse()?;
+        let service = next()?.to_string();
+        let region = next()?.to_string();
+        let account_id = next()?.to_string();
+        let resource = next()?.to_string();
+
+        Ok(Self {
+            partition,
+            service,
+            region,
+            account_id,
+            resource,
+        })
+    }
+}
+
+impl fmt::Display for Arn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "arn:{}:{}:{}:{}:{}",
+            self.partition, self.service, self.region, self.account_id, self.resource
+        )
+    }
+}
@@ -0,0 +1,134 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::error::Error;
+use crate::PostExpiration;
+
+/// Longest expiry of a presigned url S3 accepts, 7 days.
+pub const MAX_PRESIGNED_URL_EXPIRY: i32 = 7 * 24 * 3600;
+
+/// Expiries of the presigned urls and posts of a client, enforced by every presigning method,
+/// see `S3::with_presign_policy`.
+///
+/// Example:
+/// ```rust
+/// use chrono::{Duration, TimeZone, Utc};
+/// use simple_aws_s3::{PostExpiration, PresignPolicy};
+///
+/// let policy = PresignPolicy::new()
+///     .default_expiry(Duration::minutes(5))
+///     .max_expiry(Duration::minutes(15));
+/// assert_eq!(policy.default_seconds(), 300);
+/// assert_eq!(policy.url_expiry(600).unwrap(), 600);
+/// assert!(policy.url_expiry(3600).is_err());
+///
+/// // Without maximum, the limits of S3 still apply
+/// assert!(PresignPolicy::new().url_expiry(0).is_err());
+/// assert!(PresignPolicy::new().url_expiry(604801).is_err());
+/// assert_eq!(PresignPolicy::new().clamp(true).url_expiry(604801).unwrap(), 604800);
+///
+/// let now = Utc.ymd(2021, 6, 1).and_hms(10, 0, 0);
+/// let clamped = policy.clamp(true).post_expiration(Duration::hours(1).into(), now).unwrap();
+/// assert_eq!(clamped, PostExpiration::At(Utc.ymd(2021, 6, 1).and_hms(10, 15, 0)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PresignPolicy {
+    /// Expiry of the call sites without one of their own, see `S3::default_presign_expiry`
+    pub default_expiry: Duration,
+    /// Longest expiry allowed, `None` to only apply the limits of S3 (1 second to 7 days for urls)
+    pub max_expiry: Option<Duration>,
+    /// Shorten longer expiries to `max_expiry` instead of failing with `Error::InvalidInput`
+    pub clamp: bool,
+}
+
+impl Default for PresignPolicy {
+    fn default() -> Self {
+        Self {
+            default_expiry: Duration::hours(1),
+            max_expiry: None,
+            clamp: false,
+        }
+    }
+}
+
+impl PresignPolicy {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn default_expiry(mut self, expiry: Duration) -> Self {
+        self.default_expiry = expiry;
+        self
+    }
+
+    #[inline]
+    pub fn max_expiry(mut self, expiry: Duration) -> Self {
+        self.max_expiry = Some(expiry);
+        self
+    }
+
+    #[inline]
+    pub fn clamp(mut self, clamp: bool) -> Self {
+        self.clamp = clamp;
+        self
+    }
+
+    /// Default expiry in seconds, as taken by the presigned urls, clamped to the limits of S3.
+    #[inline]
+    pub fn default_seconds(&self) -> i32 {
+        self.default_expiry
+            .num_seconds()
+            .clamp(1, i64::from(MAX_PRESIGNED_URL_EXPIRY)) as i32
+    }
+
+    /// Expiry of a presigned url asked to expire in `seconds`, which must be positive.
+    #[inline]
+    pub fn url_expiry(&self, seconds: i32) -> Result<i32, Error> {
+        if seconds < 1 {
+            return Err(Error::InvalidInput(format!(
+                "Expiry of {} seconds is not positive",
+                seconds
+            )));
+        }
+        let s3_max = i64::from(MAX_PRESIGNED_URL_EXPIRY);
+        let max = self
+            .max_expiry
+            .map_or(s3_max, |max| max.num_seconds().clamp(1, s3_max));
+        if i64::from(seconds) <= max {
+            Ok(seconds)
+        } else if self.clamp {
+            Ok(max as i32)
+        } else {
+            Err(Error::InvalidInput(format!(
+                "Expiry of {} seconds is longer than the maximum of {} seconds",
+                seconds, max
+            )))
+        }
+    }
+
+    /// Expiration of a presigned post generated at `now`.
+    #[inline]
+    pub fn post_expiration(
+        &self,
+        expire_on: PostExpiration,
+        now: DateTime<Utc>,
+    ) -> Result<PostExpiration, Error> {
+        let max = match self.max_expiry {
+            Some(max) => max,
+            None => return Ok(expire_on),
+        };
+        let expiration = expire_on.at(now);
+        if expiration <= now + max {
+            Ok(expire_on)
+        } else if self.clamp {
+            Ok(PostExpiration::At(now + max))
+        } else {
+            Err(Error::InvalidInput(format!(
+                "Expiration at {} is later than the maximum of {} seconds",
+                expiration,
+                max.num_seconds()
+            )))
+        }
+    }
+}
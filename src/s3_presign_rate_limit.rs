@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use crate::error::Error;
+
+/// Storage of the presign counters of a `PresignRateLimit`, e.g. shared by the instances of a
+/// service through Redis.
+pub trait IssueCounterStore: Send + Sync {
+    /// Count one more presign of `counter` in the window starting at `window_start`, and return
+    /// the count of the window, including this one. Counts of previous windows are discarded.
+    fn increment(&self, counter: &str, window_start: DateTime<Utc>) -> Result<u64, Error>;
+}
+
+/// Counters kept in the memory of the process, the default store.
+#[derive(Debug, Default)]
+pub struct MemoryIssueCounterStore {
+    windows: Mutex<HashMap<String, (DateTime<Utc>, u64)>>,
+}
+
+impl MemoryIssueCounterStore {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl IssueCounterStore for MemoryIssueCounterStore {
+    fn increment(&self, counter: &str, window_start: DateTime<Utc>) -> Result<u64, Error> {
+        let mut windows = self
+            .windows
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if !windows.contains_key(counter) {
+            // Forget the counters of the previous windows, so the store does not grow forever
+            windows.retain(|_, (start, _)| *start >= window_start);
+        }
+        let window = windows
+            .entry(counter.to_string())
+            .or_insert((window_start, 0));
+        if window.0 != window_start {
+            *window = (window_start, 0);
+        }
+        window.1 += 1;
+        Ok(window.1)
+    }
+}
+
+/// What the presigns of a `PresignRateLimit` are counted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitScope {
+    /// The key of the object
+    Key,
+    /// The prefix of the key up to its last `/`, e.g. `uploads/42/` for `uploads/42/a.png`
+    Prefix,
+}
+
+/// Limit of the presigned urls and posts a client issues per key or prefix in a time window,
+/// enforced by every presigning method, see `S3::with_presign_rate_limit`.
+///
+/// Windows are fixed and aligned on the unix epoch, and rejected presigns count as well, so a
+/// caller retrying in a loop stays limited until the next window.
+///
+/// Example:
+/// ```rust
+/// use chrono::{Duration, TimeZone, Utc};
+/// use simple_aws_s3::error::Error;
+/// use simple_aws_s3::{PresignRateLimit, RateLimitScope};
+///
+/// let limit = PresignRateLimit::new(2, Duration::minutes(1)).scope(RateLimitScope::Prefix);
+/// let now = Utc.ymd(2021, 6, 1).and_hms(10, 0, 30);
+/// assert!(limit.check("uploads/42/a.png", now).is_ok());
+/// assert!(limit.check("uploads/42/b.png", now).is_ok());
+/// assert!(matches!(limit.check("uploads/42/c.png", now), Err(Error::RateLimited { .. })));
+/// assert!(limit.check("uploads/43/a.png", now).is_ok());
+///
+/// // Next window
+/// assert!(limit.check("uploads/42/c.png", now + Duration::seconds(30)).is_ok());
+/// ```
+#[derive(Clone)]
+pub struct PresignRateLimit {
+    /// Presigns allowed per key or prefix in a window
+    pub max: u64,
+    pub window: Duration,
+    pub scope: RateLimitScope,
+    store: Arc<dyn IssueCounterStore>,
+}
+
+impl fmt::Debug for PresignRateLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PresignRateLimit")
+            .field("max", &self.max)
+            .field("window", &self.window)
+            .field("scope", &self.scope)
+            .finish()
+    }
+}
+
+impl PresignRateLimit {
+    /// Limit of `max` presigns per key and `window`, counted in memory.
+    #[inline]
+    pub fn new(max: u64, window: Duration) -> Self {
+        Self {
+            max,
+            window,
+            scope: RateLimitScope::Key,
+            store: Arc::new(MemoryIssueCounterStore::new()),
+        }
+    }
+
+    #[inline]
+    pub fn scope(mut self, scope: RateLimitScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    #[inline]
+    pub fn store(mut self, store: impl IssueCounterStore + 'static) -> Self {
+        self.store = Arc::new(store);
+        self
+    }
+
+    /// Counter of the presigns of `key`.
+    #[inline]
+    pub fn counter<'a>(&self, key: &'a str) -> &'a str {
+        match self.scope {
+            RateLimitScope::Key => key,
+            RateLimitScope::Prefix => key.rfind('/').map_or("", |i| &key[..=i]),
+        }
+    }
+
+    /// Start of the window of `now`.
+    #[inline]
+    pub fn window_start(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let window = self.window.num_seconds().max(1);
+        let timestamp = now.timestamp();
+        Utc.timestamp(timestamp - timestamp.rem_euclid(window), 0)
+    }
+
+    /// Count a presign of `key` at `now`, fails with `Error::RateLimited` once the counter of
+    /// the key is over the limit.
+    pub fn check(&self, key: &str, now: DateTime<Utc>) -> Result<(), Error> {
+        let counter = self.counter(key);
+        let window_start = self.window_start(now);
+        if self.store.increment(counter, window_start)? > self.max {
+            return Err(Error::RateLimited {
+                counter: counter.to_string(),
+                retry_at: window_start + Duration::seconds(self.window.num_seconds().max(1)),
+            });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,229 @@
+use std::convert::TryInto;
+use std::fmt;
+use std::pin::Pin;
+
+use bytes::Bytes;
+use futures_util::stream::{self, Stream};
+use reqwest::header::{
+    HeaderMap, HeaderName, ACCEPT_RANGES, CACHE_CONTROL, CONTENT_DISPOSITION, CONTENT_ENCODING,
+    CONTENT_LANGUAGE, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, ETAG, EXPIRES, IF_MATCH,
+    IF_MODIFIED_SINCE, IF_NONE_MATCH, IF_UNMODIFIED_SINCE, LAST_MODIFIED, RANGE,
+};
+use reqwest::{Method, Response, StatusCode};
+
+use crate::error::Error;
+use crate::{GetObjectOutput, RequestOptions, S3Key, S3};
+
+/// Headers of an object passed through to the client of a proxy.
+pub const PROXIED_HEADERS: &[HeaderName] = &[
+    ACCEPT_RANGES,
+    CACHE_CONTROL,
+    CONTENT_DISPOSITION,
+    CONTENT_ENCODING,
+    CONTENT_LANGUAGE,
+    CONTENT_LENGTH,
+    CONTENT_RANGE,
+    CONTENT_TYPE,
+    ETAG,
+    EXPIRES,
+    LAST_MODIFIED,
+];
+
+/// Statuses of S3 answered to the client of a proxy as is, with an empty body and the headers
+/// the client needs: the size of the object in the `Content-Range` of a `416`, as RFC 7233
+/// requires, and the validators of the object on a `412`.
+const PROXIED_ERROR_STATUSES: &[(StatusCode, &[HeaderName])] = &[
+    (StatusCode::NOT_FOUND, &[]),
+    (StatusCode::PRECONDITION_FAILED, &[ETAG, LAST_MODIFIED]),
+    (StatusCode::RANGE_NOT_SATISFIABLE, &[CONTENT_RANGE]),
+];
+
+/// Body of an object, streamed as it is downloaded.
+pub type ByteStream = Pin<Box<dyn Stream<Item = Result<Bytes, Error>> + Send>>;
+
+/// Response of a proxy serving an object: the status and the headers of S3 (see
+/// `PROXIED_HEADERS`), and the body streamed from S3.
+pub struct ProxyResponse {
+    /// `200 OK`, `206 Partial Content` for a range, `304 Not Modified` with the headers of the
+    /// object, or `404 Not Found`, `412 Precondition Failed` and `416 Range Not Satisfiable`
+    /// with an empty body and the `Content-Range` of the `416`, the `ETag` and `Last-Modified`
+    /// of the `412`
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: ByteStream,
+}
+
+impl ProxyResponse {
+    /// Response passing through `res`, a successful response of S3.
+    #[inline]
+    pub fn from_response(res: Response) -> Self {
+        let status = res.status();
+        let headers = proxied_headers(res.headers());
+        let object = GetObjectOutput::from_response(res);
+        Self {
+            status,
+            headers,
+            body: Box::pin(object.into_stream()),
+        }
+    }
+
+    /// Response of `status` without body.
+    #[inline]
+    pub fn empty(status: StatusCode, headers: HeaderMap) -> Self {
+        Self {
+            status,
+            headers,
+            body: Box::pin(stream::empty()),
+        }
+    }
+}
+
+impl fmt::Debug for ProxyResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProxyResponse")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .finish()
+    }
+}
+
+/// Options forwarding the `Range` and conditional headers (`If-None-Match`, `If-Modified-Since`,
+/// `If-Match`, `If-Unmodified-Since`) of the request of a client.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::forwarded_options;
+/// use simple_aws_s3::prelude::reqwest::header::HeaderMap;
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("if-none-match", "\"599bab3ed2c697f1d26842727561fd94\"".parse().unwrap());
+/// headers.insert("if-modified-since", "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap());
+/// headers.insert("cookie", "session=42".parse().unwrap());
+///
+/// let options = forwarded_options(&headers);
+/// assert_eq!(options.if_none_match.as_deref(), Some("\"599bab3ed2c697f1d26842727561fd94\""));
+/// assert_eq!(options.if_modified_since.as_deref(), Some("Wed, 21 Oct 2015 07:28:00 GMT"));
+/// assert!(options.headers.is_empty());
+/// ```
+#[inline]
+pub fn forwarded_options(headers: &HeaderMap) -> RequestOptions {
+    let header = |name| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from)
+    };
+    let mut options = RequestOptions::new();
+    options.range = header(RANGE);
+    options.if_none_match = header(IF_NONE_MATCH);
+    options.if_modified_since = header(IF_MODIFIED_SINCE);
+    options.if_match = header(IF_MATCH);
+    options.if_unmodified_since = header(IF_UNMODIFIED_SINCE);
+    options
+}
+
+/// Headers of `headers` to pass through to the client.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::proxied_headers;
+/// use simple_aws_s3::prelude::reqwest::header::HeaderMap;
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("content-range", "bytes 0-1023/4096".parse().unwrap());
+/// headers.insert("etag", "\"599bab3ed2c697f1d26842727561fd94\"".parse().unwrap());
+/// headers.insert("x-amz-request-id", "4442587FB7D0A2F9".parse().unwrap());
+///
+/// let proxied = proxied_headers(&headers);
+/// assert_eq!(proxied["content-range"], "bytes 0-1023/4096");
+/// assert!(proxied.contains_key("etag"));
+/// assert!(!proxied.contains_key("x-amz-request-id"));
+/// ```
+#[inline]
+pub fn proxied_headers(headers: &HeaderMap) -> HeaderMap {
+    PROXIED_HEADERS
+        .iter()
+        .filter_map(|name| Some((name.clone(), headers.get(name)?.clone())))
+        .collect()
+}
+
+impl S3 {
+    /// Stream an object to the client of an http service, e.g. a download endpoint in front of
+    /// a private bucket. `range` is the `Range` header of the client, if any.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::S3;
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let res = s3.stream_to_http("videos/intro.mp4", Some("bytes=0-1048575")).await?;
+    /// println!("{} {:?}", res.status, res.headers.get("content-range"));
+    /// // Hand `res.status`, `res.headers` and the `res.body` stream to the http framework
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn stream_to_http(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        range: Option<&str>,
+    ) -> Result<ProxyResponse, Error> {
+        let mut options = RequestOptions::new();
+        options.range = range.map(String::from);
+        self.proxy_object(key, &options).await
+    }
+
+    /// Same as `stream_to_http`, forwarding the `Range` and conditional headers of the client
+    /// (see `forwarded_options`). When the cached copy of the client is still valid, S3 answers
+    /// `304 Not Modified` and no byte of the object is downloaded.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::S3;
+    /// use simple_aws_s3::prelude::reqwest::StatusCode;
+    /// use simple_aws_s3::prelude::reqwest::header::HeaderMap;
+    ///
+    /// # async fn run(s3: S3, client_headers: HeaderMap) -> Result<(), simple_aws_s3::error::Error> {
+    /// let res = s3.stream_to_http_conditional("assets/app.js", &client_headers).await?;
+    /// if res.status == StatusCode::NOT_MODIFIED {
+    ///     println!("served from the cache of the client, etag {:?}", res.headers.get("etag"));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn stream_to_http_conditional(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        client_headers: &HeaderMap,
+    ) -> Result<ProxyResponse, Error> {
+        self.proxy_object(key, &forwarded_options(client_headers))
+            .await
+    }
+
+    #[inline]
+    pub(crate) async fn proxy_object(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        options: &RequestOptions,
+    ) -> Result<ProxyResponse, Error> {
+        let req = self.prepare_object_request(key, Method::GET, options)?;
+        let mut accepted = vec![StatusCode::NOT_MODIFIED];
+        accepted.extend(PROXIED_ERROR_STATUSES.iter().map(|(status, _)| *status));
+        let res = self.execute_accepting(req, &accepted).await?;
+
+        match PROXIED_ERROR_STATUSES
+            .iter()
+            .find(|(status, _)| *status == res.status())
+        {
+            Some((status, names)) => {
+                let headers = names
+                    .iter()
+                    .filter_map(|name| Some((name.clone(), res.headers().get(name)?.clone())))
+                    .collect();
+                Ok(ProxyResponse::empty(*status, headers))
+            }
+            None => Ok(ProxyResponse::from_response(res)),
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use reqwest::Method;
+
+use crate::error::Error;
+use crate::{RequestOptions, S3};
+
+/// Public access block of a bucket: settings overriding ACLs and policies making it public.
+///
+/// Ref: https://docs.aws.amazon.com/AmazonS3/latest/userguide/access-control-block-public-access.html
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::PublicAccessBlock;
+///
+/// let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <PublicAccessBlockConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+///   <BlockPublicAcls>true</BlockPublicAcls>
+///   <IgnorePublicAcls>true</IgnorePublicAcls>
+///   <BlockPublicPolicy>true</BlockPublicPolicy>
+///   <RestrictPublicBuckets>true</RestrictPublicBuckets>
+/// </PublicAccessBlockConfiguration>"#;
+///
+/// let block = PublicAccessBlock::from_xml(body).unwrap();
+/// assert_eq!(block, PublicAccessBlock::all());
+/// assert!(block.to_xml().contains("<RestrictPublicBuckets>true</RestrictPublicBuckets>"));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PublicAccessBlock {
+    /// Reject requests setting public ACLs
+    #[serde(default)]
+    pub block_public_acls: bool,
+    /// Ignore the public ACLs of the bucket and its objects
+    #[serde(default)]
+    pub ignore_public_acls: bool,
+    /// Reject bucket policies granting public access
+    #[serde(default)]
+    pub block_public_policy: bool,
+    /// Restrict access to buckets with a public policy to AWS services and the bucket owner
+    #[serde(default)]
+    pub restrict_public_buckets: bool,
+}
+
+impl PublicAccessBlock {
+    /// Every public access blocked, the default of new buckets.
+    #[inline]
+    pub fn all() -> Self {
+        Self {
+            block_public_acls: true,
+            ignore_public_acls: true,
+            block_public_policy: true,
+            restrict_public_buckets: true,
+        }
+    }
+
+    #[inline]
+    pub fn from_xml(body: &str) -> Result<Self, Error> {
+        Ok(serde_xml_rs::from_str(body)?)
+    }
+
+    #[inline]
+    pub fn to_xml(&self) -> String {
+        format!(
+            r#"<PublicAccessBlockConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><BlockPublicAcls>{}</BlockPublicAcls><IgnorePublicAcls>{}</IgnorePublicAcls><BlockPublicPolicy>{}</BlockPublicPolicy><RestrictPublicBuckets>{}</RestrictPublicBuckets></PublicAccessBlockConfiguration>"#,
+            self.block_public_acls,
+            self.ignore_public_acls,
+            self.block_public_policy,
+            self.restrict_public_buckets,
+        )
+    }
+}
+
+impl S3 {
+    /// Get the public access block of the bucket, `None` if it has none.
+    #[inline]
+    pub async fn get_public_access_block(&self) -> Result<Option<PublicAccessBlock>, Error> {
+        let req = self.prepare_request(
+            Method::GET,
+            None,
+            &[("publicAccessBlock", "")],
+            &RequestOptions::default(),
+        )?;
+        match self.execute(req).await {
+            Ok(res) => Ok(Some(PublicAccessBlock::from_xml(&res.text().await?)?)),
+            // NoSuchPublicAccessBlockConfiguration
+            Err(Error::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Set the public access block of the bucket.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::{PublicAccessBlock, S3};
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// s3.put_public_access_block(&PublicAccessBlock::all()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn put_public_access_block(&self, block: &PublicAccessBlock) -> Result<(), Error> {
+        let req = self.prepare_xml_request(
+            Method::PUT,
+            None,
+            &[("publicAccessBlock", "")],
+            block.to_xml(),
+        )?;
+        self.execute(req).await?;
+        Ok(())
+    }
+
+    #[inline]
+    pub async fn delete_public_access_block(&self) -> Result<(), Error> {
+        let req = self.prepare_request(
+            Method::DELETE,
+            None,
+            &[("publicAccessBlock", "")],
+            &RequestOptions::default(),
+        )?;
+        self.execute(req).await?;
+        Ok(())
+    }
+}
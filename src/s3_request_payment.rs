@@ -0,0 +1,114 @@
+use std::fmt;
+use std::str::FromStr;
+
+use reqwest::Method;
+
+use crate::error::Error;
+use crate::{RequestOptions, S3};
+
+/// Who pays for the requests and the data transfer of a bucket.
+///
+/// Ref: https://docs.aws.amazon.com/AmazonS3/latest/userguide/RequesterPaysBuckets.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Payer {
+    BucketOwner,
+    /// Requester pays, requests must be sent with `x-amz-request-payer: requester`
+    Requester,
+}
+
+impl Payer {
+    #[inline]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::BucketOwner => "BucketOwner",
+            Self::Requester => "Requester",
+        }
+    }
+
+    /// Parse a GetBucketRequestPayment response.
+    ///
+    /// Example:
+    /// ```rust
+    /// use simple_aws_s3::Payer;
+    ///
+    /// let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+    /// <RequestPaymentConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+    ///   <Payer>Requester</Payer>
+    /// </RequestPaymentConfiguration>"#;
+    ///
+    /// let payer = Payer::from_xml(body).unwrap();
+    /// assert_eq!(payer, Payer::Requester);
+    /// assert!(payer.to_xml().contains("<Payer>Requester</Payer>"));
+    /// ```
+    #[inline]
+    pub fn from_xml(body: &str) -> Result<Self, Error> {
+        let config: RequestPaymentConfiguration = serde_xml_rs::from_str(body)?;
+        config.payer.parse()
+    }
+
+    #[inline]
+    pub fn to_xml(&self) -> String {
+        format!(
+            r#"<RequestPaymentConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Payer>{}</Payer></RequestPaymentConfiguration>"#,
+            self.as_str()
+        )
+    }
+}
+
+impl fmt::Display for Payer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Payer {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "BucketOwner" => Ok(Self::BucketOwner),
+            "Requester" => Ok(Self::Requester),
+            _ => Err(Error::InvalidInput(format!("Unknown payer {:?}", s))),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RequestPaymentConfiguration {
+    payer: String,
+}
+
+impl S3 {
+    /// Get who pays for the requests of the bucket.
+    #[inline]
+    pub async fn get_bucket_request_payment(&self) -> Result<Payer, Error> {
+        let req = self.prepare_request(
+            Method::GET,
+            None,
+            &[("requestPayment", "")],
+            &RequestOptions::default(),
+        )?;
+        let res = self.execute(req).await?;
+        Payer::from_xml(&res.text().await?)
+    }
+
+    /// Set who pays for the requests of the bucket, e.g. to share a dataset as requester pays.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::{Payer, S3};
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// s3.put_bucket_request_payment(Payer::Requester).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn put_bucket_request_payment(&self, payer: Payer) -> Result<(), Error> {
+        let req =
+            self.prepare_xml_request(Method::PUT, None, &[("requestPayment", "")], payer.to_xml())?;
+        self.execute(req).await?;
+        Ok(())
+    }
+}
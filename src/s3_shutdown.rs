@@ -0,0 +1,237 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use tokio::sync::Notify;
+
+use crate::error::Error;
+use crate::{S3Key, S3};
+
+/// A multipart upload started by the client, neither completed nor aborted yet.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PendingUpload {
+    pub key: String,
+    pub upload_id: String,
+}
+
+/// Result of `S3::shutdown`.
+#[derive(Debug, Default)]
+pub struct ShutdownReport {
+    /// Every request and upload in flight completed before the timeout
+    pub drained: bool,
+    /// Multipart uploads aborted: the ones whose operation was cancelled, and the ones still in
+    /// progress at the timeout
+    pub aborted_uploads: Vec<PendingUpload>,
+    /// Multipart uploads which could not be aborted, their parts are stored until a lifecycle
+    /// rule aborts them
+    pub failed_aborts: Vec<(PendingUpload, Error)>,
+}
+
+impl ShutdownReport {
+    /// Whether every transfer completed, and nothing is left behind in the bucket.
+    #[inline]
+    pub fn is_clean(&self) -> bool {
+        self.drained && self.failed_aborts.is_empty()
+    }
+}
+
+/// Requests and multipart uploads in flight of a client and its clones.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct InFlight(Arc<InFlightState>);
+
+#[derive(Debug, Default)]
+struct InFlightState {
+    transfers: Mutex<Transfers>,
+    /// Notified when the last transfer completes
+    idle: Notify,
+}
+
+#[derive(Debug, Default)]
+struct Transfers {
+    /// `S3::shutdown` is in progress, only the requests of the tracked uploads are sent
+    draining: bool,
+    shut_down: bool,
+    requests: usize,
+    uploads: Vec<PendingUpload>,
+    /// Uploads whose operation was dropped before completing or aborting them
+    abandoned: Vec<PendingUpload>,
+}
+
+impl Transfers {
+    #[inline]
+    fn is_idle(&self) -> bool {
+        self.requests == 0 && self.uploads.is_empty()
+    }
+
+    /// Whether `upload_id` is an upload still to complete or abort.
+    #[inline]
+    fn is_tracked(&self, upload_id: &str) -> bool {
+        self.uploads
+            .iter()
+            .chain(&self.abandoned)
+            .any(|upload| upload.upload_id == upload_id)
+    }
+}
+
+impl InFlight {
+    #[inline]
+    fn transfers(&self) -> MutexGuard<'_, Transfers> {
+        // Transfers are only counted under the lock, they stay consistent after a panic
+        self.0
+            .transfers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Count a request until the guard is dropped, fails once the client is shut down. While
+    /// draining, only requests on the tracked uploads (`upload_id`) are let through, so uploads
+    /// in flight can complete and abandoned ones be aborted.
+    #[inline]
+    pub(crate) fn start_request(&self, upload_id: Option<&str>) -> Result<RequestGuard, Error> {
+        let mut transfers = self.transfers();
+        let tracked = matches!(upload_id, Some(upload_id) if transfers.is_tracked(upload_id));
+        if transfers.shut_down || (transfers.draining && !tracked) {
+            return Err(Error::ShutDown);
+        }
+        transfers.requests += 1;
+        Ok(RequestGuard {
+            in_flight: self.clone(),
+        })
+    }
+
+    /// Track a multipart upload until `UploadGuard::finish`. An upload dropped before is left to
+    /// `S3::shutdown` to abort.
+    #[inline]
+    pub(crate) fn start_upload(&self, key: &str, upload_id: &str) -> UploadGuard {
+        let upload = PendingUpload {
+            key: key.to_string(),
+            upload_id: upload_id.to_string(),
+        };
+        self.transfers().uploads.push(upload.clone());
+        UploadGuard {
+            in_flight: self.clone(),
+            upload,
+            finished: false,
+        }
+    }
+
+    /// Wait until no request nor upload is in flight.
+    async fn idle(&self) {
+        loop {
+            // Registered before checking, so a transfer completing meanwhile is not missed
+            let notified = self.0.idle.notified();
+            if self.transfers().is_idle() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Request in flight, released with its response once the body is read.
+pub(crate) struct RequestGuard {
+    in_flight: InFlight,
+}
+
+impl Drop for RequestGuard {
+    fn drop(&mut self) {
+        let mut transfers = self.in_flight.transfers();
+        transfers.requests -= 1;
+        if transfers.is_idle() {
+            self.in_flight.0.idle.notify_waiters();
+        }
+    }
+}
+
+pub(crate) struct UploadGuard {
+    in_flight: InFlight,
+    upload: PendingUpload,
+    finished: bool,
+}
+
+impl UploadGuard {
+    /// The upload was completed or aborted.
+    #[inline]
+    pub(crate) fn finish(mut self) {
+        self.finished = true;
+    }
+}
+
+impl Drop for UploadGuard {
+    fn drop(&mut self) {
+        let mut transfers = self.in_flight.transfers();
+        transfers.uploads.retain(|upload| *upload != self.upload);
+        if !self.finished {
+            transfers.abandoned.push(self.upload.clone());
+        }
+        if transfers.is_idle() {
+            self.in_flight.0.idle.notify_waiters();
+        }
+    }
+}
+
+impl S3 {
+    /// Drain the client, and its clones, before the process exits: fail every new request with
+    /// `Error::ShutDown`, wait up to `timeout` for the requests (until their body is read) and
+    /// uploads in flight, then abort the multipart uploads left behind.
+    ///
+    /// Multipart uploads left behind are the ones of `put_object_from_stream` which were
+    /// cancelled (their future dropped) or still in progress at the timeout. Uploads started
+    /// with `create_multipart_upload` belong to the caller, and are never aborted.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use simple_aws_s3::S3;
+    ///
+    /// # async fn run(s3: S3) {
+    /// tokio::signal::ctrl_c().await.unwrap();
+    /// let report = s3.shutdown(Duration::from_secs(25)).await;
+    /// for (upload, e) in &report.failed_aborts {
+    ///     eprintln!("{} left behind: {}", upload.key, e);
+    /// }
+    /// # }
+    /// ```
+    pub async fn shutdown(&self, timeout: Duration) -> ShutdownReport {
+        let in_flight = self.in_flight();
+        in_flight.transfers().draining = true;
+        let drained = tokio::time::timeout(timeout, in_flight.idle())
+            .await
+            .is_ok();
+
+        // Still tracked while aborted, so their aborts go through
+        let uploads = {
+            let transfers = in_flight.transfers();
+            let mut uploads = transfers.abandoned.clone();
+            if !drained {
+                uploads.extend(transfers.uploads.iter().cloned());
+            }
+            uploads
+        };
+        let mut report = ShutdownReport {
+            drained,
+            ..ShutdownReport::default()
+        };
+        for upload in uploads {
+            let aborted = match S3Key::lenient(upload.key.as_str()) {
+                Ok(key) => self.abort_multipart_upload(key, &upload.upload_id).await,
+                Err(e) => Err(e),
+            };
+            match aborted {
+                Ok(()) => report.aborted_uploads.push(upload),
+                Err(e) => report.failed_aborts.push((upload, e)),
+            }
+        }
+
+        let mut transfers = in_flight.transfers();
+        transfers.abandoned.clear();
+        transfers.shut_down = true;
+        drop(transfers);
+        report
+    }
+
+    /// Whether `shutdown` completed, so the client no longer sends requests.
+    #[inline]
+    pub fn is_shut_down(&self) -> bool {
+        self.in_flight().transfers().shut_down
+    }
+}
@@ -30,16 +30,32 @@ type HmacSha256 = Hmac<Sha256>;
 ///
 /// assert_eq!(signature, expected_signature);
 /// ```
+///
+/// The signer is not tied to S3, requests of other AWS services are signed the same way with
+/// their service name, see [`CanonicalRequest`](crate::CanonicalRequest) for an example.
 #[derive(Debug, Clone)]
 pub struct Signer<'s> {
     secret_key: &'s str,
     region: &'s str,
+    service: &'s str,
 }
 
 impl<'s> Signer<'s> {
+    /// Signer of S3 requests.
     #[inline]
     pub fn new(secret_key: &'s str, region: &'s str) -> Self {
-        Self { secret_key, region }
+        Self {
+            secret_key,
+            region,
+            service: "s3",
+        }
+    }
+
+    /// Sign requests of another AWS service, e.g. `sts` or `sqs`.
+    #[inline]
+    pub fn with_service(mut self, service: &'s str) -> Self {
+        self.service = service;
+        self
     }
 
     #[inline]
@@ -51,7 +67,7 @@ impl<'s> Signer<'s> {
         let mut key = self.signing_hasher(date)?;
         key.update(string_to_sign.as_bytes());
         let msg = key.finalize().into_bytes();
-        Ok(hex::encode(&msg))
+        Ok(hex::encode(msg))
     }
 
     #[inline]
@@ -69,7 +85,7 @@ impl<'s> Signer<'s> {
 
         // Step 3: Sign Date and region and Service
         let mut date_region_service_h = HmacSha256::new_from_slice(&date_region_key)?;
-        date_region_service_h.update(b"s3");
+        date_region_service_h.update(self.service.as_bytes());
         let date_region_service_key = date_region_service_h.finalize().into_bytes();
 
         // Step 4: Final sign
@@ -0,0 +1,114 @@
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::Utc;
+use tokio::runtime::Handle;
+
+use crate::error::Error;
+use crate::{PutObjectOutput, RequestOptions, S3Key, S3Object, S3};
+
+/// Objects created by the process, making the keys of temporary objects unique.
+static TEMP_OBJECT_COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// Scratch object deleted when the guard is dropped, e.g. the fixture of a test or an
+/// intermediate artifact of a pipeline.
+///
+/// Dropping the guard spawns the deletion on the current Tokio runtime, without waiting for it.
+/// Prefer `delete` where the deletion must be complete, or its error known: outside of a
+/// runtime, or if the runtime stops first, a dropped object is left behind. A lifecycle rule
+/// expiring the prefix cleans such leftovers.
+///
+/// Example:
+/// ```rust,no_run
+/// use simple_aws_s3::{TempObject, S3};
+///
+/// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+/// let input = TempObject::create(&s3, "tmp/pipeline/", b"a,b\n1,2\n".to_vec()).await?;
+/// let bytes = s3.get_object_bytes(input.key()).await?;
+/// assert_eq!(bytes.map(|bytes| bytes.len()), Some(8));
+///
+/// // Deleted once done with it
+/// input.delete().await?;
+///
+/// // Kept as the result of the pipeline
+/// let output = TempObject::create(&s3, "tmp/pipeline/", b"3\n".to_vec()).await?;
+/// let key = output.persist();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TempObject {
+    s3: S3,
+    key: S3Key,
+    output: PutObjectOutput,
+    /// Whether the object is deleted on drop
+    armed: bool,
+}
+
+impl TempObject {
+    /// Upload `body` as a new object with a unique key under `prefix`.
+    pub async fn create(s3: &S3, prefix: &str, body: Vec<u8>) -> Result<Self, Error> {
+        let key = S3Key::new(format!(
+            "{}{}-{}-{}",
+            prefix,
+            Utc::now().format("%Y%m%dT%H%M%S%.6fZ"),
+            process::id(),
+            TEMP_OBJECT_COUNT.fetch_add(1, Ordering::Relaxed)
+        ))?;
+        let output = s3
+            .put_object_with_options(&key, body, &RequestOptions::default())
+            .await?;
+        Ok(Self {
+            s3: s3.clone(),
+            key,
+            output,
+            armed: true,
+        })
+    }
+
+    #[inline]
+    pub fn key(&self) -> &S3Key {
+        &self.key
+    }
+
+    /// Result of the upload of the object, e.g. its ETag.
+    #[inline]
+    pub fn output(&self) -> &PutObjectOutput {
+        &self.output
+    }
+
+    #[inline]
+    pub fn object(&self) -> S3Object {
+        S3Object::new(self.s3.clone(), self.key.clone())
+    }
+
+    /// Keep the object, and return its key.
+    #[inline]
+    pub fn persist(mut self) -> S3Key {
+        self.armed = false;
+        self.key.clone()
+    }
+
+    /// Delete the object now, and wait for the deletion.
+    pub async fn delete(mut self) -> Result<(), Error> {
+        self.armed = false;
+        self.s3.delete_object(&self.key).await?;
+        Ok(())
+    }
+}
+
+impl Drop for TempObject {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        if let Ok(runtime) = Handle::try_current() {
+            let s3 = self.s3.clone();
+            let key = self.key.clone();
+            runtime.spawn(async move {
+                // Nobody is left to handle the error, the object expires with its prefix
+                let _ = s3.delete_object(&key).await;
+            });
+        }
+    }
+}
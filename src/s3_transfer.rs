@@ -0,0 +1,275 @@
+use std::mem;
+use std::sync::{Arc, Mutex};
+
+use futures_util::stream::StreamExt;
+use reqwest::header::{CACHE_CONTROL, CONTENT_DISPOSITION};
+use tokio::sync::Semaphore;
+
+use crate::error::Error;
+use crate::s3_output::header_str;
+use crate::{
+    GetObjectOutput, ListObjectsOptions, PartSizeOptions, RequestOptions, S3Key, MIN_PART_SIZE, S3,
+};
+
+/// Options of `S3::transfer_prefix`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferOptions {
+    /// Prefix replacing the source prefix in the destination keys, the same keys by default
+    pub destination_prefix: Option<String>,
+    /// Number of objects copied concurrently
+    pub concurrency: usize,
+    /// Part sizes of the objects uploaded with a multipart upload
+    pub part_sizes: PartSizeOptions,
+}
+
+impl Default for TransferOptions {
+    fn default() -> Self {
+        Self {
+            destination_prefix: None,
+            concurrency: 8,
+            part_sizes: PartSizeOptions::new(),
+        }
+    }
+}
+
+/// Result of `S3::transfer_prefix`.
+#[derive(Debug, Default)]
+pub struct TransferReport {
+    /// Number of objects copied
+    pub copied: usize,
+    /// Bytes of the objects copied
+    pub bytes_copied: u64,
+    /// Objects listed but deleted before they were copied
+    pub missing: Vec<String>,
+    pub failures: Vec<TransferFailure>,
+}
+
+impl TransferReport {
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// An object which could not be copied, e.g. to retry it.
+#[derive(Debug)]
+pub struct TransferFailure {
+    /// Key of the object in the source bucket
+    pub key: String,
+    pub error: Error,
+}
+
+/// Progress of `S3::transfer_prefix_with_progress`, given after each object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferProgress {
+    /// Key of the object just copied, or failed, in the source bucket
+    pub key: String,
+    /// Whether the copy of `key` failed
+    pub failed: bool,
+    /// Number of objects copied so far
+    pub copied: usize,
+    /// Bytes of the objects copied so far
+    pub bytes_copied: u64,
+    /// Number of objects failed so far
+    pub failures: usize,
+}
+
+/// Options of the upload of a copied object: the content type and encoding, caching,
+/// disposition and user metadata of the source object.
+#[inline]
+fn transfer_request_options(object: &GetObjectOutput) -> RequestOptions {
+    let mut options = RequestOptions {
+        metadata: object.metadata.clone(),
+        ..RequestOptions::default()
+    };
+    options.content_type = object.content_type.clone();
+    options.content_encoding = object.content_encoding.clone();
+    options.cache_control = header_str(object.headers(), CACHE_CONTROL.as_str()).map(String::from);
+    options.content_disposition =
+        header_str(object.headers(), CONTENT_DISPOSITION.as_str()).map(String::from);
+    options
+}
+
+/// Helpers to copy objects between buckets, e.g. of different providers.
+impl S3 {
+    /// Copy every object under `prefix` to `destination`, another client which may be of
+    /// another endpoint or provider (e.g. from AWS to Cloudflare R2), keeping their content
+    /// type and encoding, caching, disposition and user metadata. Objects are streamed from the source to a
+    /// multipart upload, only those smaller than `MIN_PART_SIZE` are read whole and put at
+    /// once.
+    ///
+    /// An object which fails is reported in `TransferReport::failures` and the others are
+    /// still copied. Only a failed listing fails the transfer, once the copies in flight end.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::{TransferOptions, S3};
+    ///
+    /// # async fn run(aws: S3, r2: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let options = TransferOptions {
+    ///     concurrency: 32,
+    ///     ..Default::default()
+    /// };
+    /// let report = aws.transfer_prefix(&r2, "media/", &options).await?;
+    /// println!("{} objects, {} bytes copied", report.copied, report.bytes_copied);
+    /// for failure in &report.failures {
+    ///     eprintln!("{}: {}", failure.key, failure.error);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn transfer_prefix(
+        &self,
+        destination: &S3,
+        prefix: &str,
+        options: &TransferOptions,
+    ) -> Result<TransferReport, Error> {
+        self.transfer_prefix_with_progress(destination, prefix, options, |_: &TransferProgress| {})
+            .await
+    }
+
+    /// Same as `transfer_prefix`, calling `progress` after each object.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::{TransferOptions, TransferProgress, S3};
+    ///
+    /// # async fn run(aws: S3, r2: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let options = TransferOptions {
+    ///     destination_prefix: Some("archive/media/".into()),
+    ///     ..Default::default()
+    /// };
+    /// let report = aws
+    ///     .transfer_prefix_with_progress(&r2, "media/", &options, |progress: &TransferProgress| {
+    ///         if progress.failed {
+    ///             eprintln!("{} failed", progress.key);
+    ///         }
+    ///         println!("{} objects, {} bytes", progress.copied, progress.bytes_copied);
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn transfer_prefix_with_progress<F>(
+        &self,
+        destination: &S3,
+        prefix: &str,
+        options: &TransferOptions,
+        progress: F,
+    ) -> Result<TransferReport, Error>
+    where
+        F: Fn(&TransferProgress) + Send + Sync + 'static,
+    {
+        let concurrency = options.concurrency.max(1);
+        let semaphore = Arc::new(Semaphore::new(concurrency));
+        let report = Arc::new(Mutex::new(TransferReport::default()));
+        let progress = Arc::new(progress);
+        let destination_prefix = options.destination_prefix.as_deref().unwrap_or(prefix);
+
+        let objects = self.list_objects_stream(&ListObjectsOptions::new().prefix(prefix));
+        futures_util::pin_mut!(objects);
+        let mut listed = Ok(());
+        while let Some(object) = objects.next().await {
+            let key = match object {
+                Ok(object) => object.key,
+                Err(e) => {
+                    listed = Err(e);
+                    break;
+                }
+            };
+            let permit = semaphore
+                .clone()
+                .acquire_owned()
+                .await
+                .expect("the semaphore is never closed");
+            let destination_key = format!(
+                "{}{}",
+                destination_prefix,
+                key.strip_prefix(prefix).unwrap_or(&key)
+            );
+            let source = self.clone();
+            let destination = destination.clone();
+            let part_sizes = options.part_sizes;
+            let report = report.clone();
+            let progress = progress.clone();
+            tokio::spawn(async move {
+                let res = source
+                    .transfer_object(&destination, &key, &destination_key, &part_sizes)
+                    .await;
+                let current = {
+                    let mut report = report.lock().unwrap();
+                    let failed = res.is_err();
+                    match res {
+                        Ok(Some(size)) => {
+                            report.copied += 1;
+                            report.bytes_copied += size;
+                        }
+                        Ok(None) => report.missing.push(key.clone()),
+                        Err(error) => report.failures.push(TransferFailure {
+                            key: key.clone(),
+                            error,
+                        }),
+                    }
+                    TransferProgress {
+                        key,
+                        failed,
+                        copied: report.copied,
+                        bytes_copied: report.bytes_copied,
+                        failures: report.failures.len(),
+                    }
+                };
+                progress(&current);
+                drop(permit);
+            });
+        }
+
+        // Every copy holds a permit until its end
+        let _all = semaphore.acquire_many(concurrency as u32).await;
+        listed?;
+        let mut report = mem::take(&mut *report.lock().unwrap());
+        report.missing.sort();
+        report.failures.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(report)
+    }
+
+    /// Copy an object to `destination`, returning its size, `None` if it no longer exists.
+    async fn transfer_object(
+        &self,
+        destination: &S3,
+        source_key: &str,
+        destination_key: &str,
+        part_sizes: &PartSizeOptions,
+    ) -> Result<Option<u64>, Error> {
+        // Listed keys may predate the key checks
+        let object = match self.get_object(S3Key::lenient(source_key)?).await? {
+            Some(object) => object,
+            None => return Ok(None),
+        };
+        let destination_key = S3Key::lenient(destination_key)?;
+        let options = transfer_request_options(&object);
+
+        match object.content_length {
+            // Too small for a multipart upload to be worth its requests
+            Some(size) if size < MIN_PART_SIZE => {
+                let body = object.bytes().await?;
+                destination
+                    .put_object_with_options(destination_key, body.to_vec(), &options)
+                    .await?;
+                Ok(Some(size))
+            }
+            _ => {
+                let mut size = 0;
+                let body = object.into_stream().inspect(|chunk| {
+                    if let Ok(chunk) = chunk {
+                        size += chunk.len() as u64;
+                    }
+                });
+                destination
+                    .put_object_from_try_stream(destination_key, body, &options, part_sizes)
+                    .await?;
+                Ok(Some(size))
+            }
+        }
+    }
+}
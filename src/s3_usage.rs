@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+
+use futures_util::stream::TryStreamExt;
+
+use crate::error::Error;
+use crate::{ListObjectsOptions, ObjectSummary, S3};
+
+/// Total size and number of objects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    /// Total size in bytes
+    pub size: u64,
+    pub count: u64,
+}
+
+impl Usage {
+    #[inline]
+    pub fn add(&mut self, object: &ObjectSummary) {
+        self.size += object.size;
+        self.count += 1;
+    }
+}
+
+/// Usage of the objects under a prefix, grouped by the next delimiter after the prefix, like the
+/// common prefixes of a listing.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::{ListObjectsOutput, StorageUsage};
+///
+/// let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+///   <Name>examplebucket</Name>
+///   <IsTruncated>false</IsTruncated>
+///   <Contents>
+///     <Key>users/index.json</Key>
+///     <LastModified>2021-06-01T10:00:00.000Z</LastModified>
+///     <ETag>"599bab3ed2c697f1d26842727561fd94"</ETag>
+///     <Size>10</Size>
+///   </Contents>
+///   <Contents>
+///     <Key>users/42/avatar.png</Key>
+///     <LastModified>2021-06-01T10:00:00.000Z</LastModified>
+///     <ETag>"599bab3ed2c697f1d26842727561fd94"</ETag>
+///     <Size>857</Size>
+///   </Contents>
+///   <Contents>
+///     <Key>users/42/photos/1.png</Key>
+///     <LastModified>2021-06-01T10:00:00.000Z</LastModified>
+///     <ETag>"599bab3ed2c697f1d26842727561fd94"</ETag>
+///     <Size>1000</Size>
+///   </Contents>
+/// </ListBucketResult>"#;
+///
+/// let mut usage = StorageUsage::new("users/", "/");
+/// for object in &ListObjectsOutput::from_xml(body).unwrap().contents {
+///     usage.add(object);
+/// }
+/// assert_eq!(usage.total.size, 1867);
+/// assert_eq!(usage.total.count, 3);
+/// assert_eq!(usage.groups["users/"].count, 1);
+/// assert_eq!(usage.groups["users/42/"].size, 1857);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StorageUsage {
+    pub prefix: String,
+    pub delimiter: String,
+    /// Usage of every object under the prefix
+    pub total: Usage,
+    /// Usage by group, the objects directly under the prefix are grouped under the prefix
+    /// itself
+    pub groups: BTreeMap<String, Usage>,
+}
+
+impl StorageUsage {
+    #[inline]
+    pub fn new(prefix: impl Into<String>, delimiter: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            delimiter: delimiter.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Count `object`, which must be under the prefix.
+    #[inline]
+    pub fn add(&mut self, object: &ObjectSummary) {
+        self.total.add(object);
+        self.groups
+            .entry(self.group(&object.key).to_string())
+            .or_default()
+            .add(object);
+    }
+
+    /// Group of `key`: the key up to the first delimiter after the prefix (included), or the
+    /// prefix.
+    #[inline]
+    fn group<'a>(&'a self, key: &'a str) -> &'a str {
+        let rest = key.strip_prefix(self.prefix.as_str()).unwrap_or(key);
+        if self.delimiter.is_empty() {
+            return &self.prefix;
+        }
+        match rest.find(self.delimiter.as_str()) {
+            Some(i) => &key[..key.len() - rest.len() + i + self.delimiter.len()],
+            None => &self.prefix,
+        }
+    }
+}
+
+/// Helpers to report the storage used by a bucket.
+impl S3 {
+    /// Total size and number of the objects under `prefix` (use `""` for the whole bucket).
+    #[inline]
+    pub async fn bucket_size(&self, prefix: &str) -> Result<Usage, Error> {
+        Ok(self.storage_usage(prefix, "").await?.total)
+    }
+
+    /// Size and number of the objects under `prefix`, grouped by the next `delimiter`, e.g. the
+    /// usage of each user under `users/`.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::S3;
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let usage = s3.storage_usage("users/", "/").await?;
+    /// for (prefix, usage) in &usage.groups {
+    ///     println!("{}: {} objects, {} bytes", prefix, usage.count, usage.size);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn storage_usage(
+        &self,
+        prefix: &str,
+        delimiter: &str,
+    ) -> Result<StorageUsage, Error> {
+        let options = ListObjectsOptions::new().prefix(prefix);
+        let mut usage = StorageUsage::new(prefix, delimiter);
+        let objects = self.list_objects_stream(&options);
+        futures_util::pin_mut!(objects);
+        while let Some(object) = objects.try_next().await? {
+            usage.add(&object);
+        }
+        Ok(usage)
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+
+use crate::error::Error;
+use crate::s3_key::try_key;
+use crate::{
+    ContentLengthCondition, ContentTypeCondition, HeadObjectOutput, RequestOptions, S3Key, S3,
+};
+
+/// What an object uploaded by a client must look like, usually what its presigned post or put
+/// authorized.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::{ContentTypeCondition, HeadObjectOutput, UploadExpectation, UploadMismatch};
+/// use simple_aws_s3::prelude::reqwest::header::HeaderMap;
+///
+/// let expected = UploadExpectation::new()
+///     .content_length(1024)
+///     .content_type(ContentTypeCondition::StartsWith("image/".into()))
+///     .metadata("user", "42");
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("content-length", "4096".parse().unwrap());
+/// headers.insert("content-type", "image/png".parse().unwrap());
+/// headers.insert("x-amz-meta-user", "43".parse().unwrap());
+/// let head = HeadObjectOutput::from_headers(headers);
+///
+/// assert_eq!(
+///     expected.check(Some(&head)),
+///     vec![
+///         UploadMismatch::SizeOutOfRange { min: 0, max: 1034, actual: 4096 },
+///         UploadMismatch::Metadata { name: "user".into(), expected: "42".into(), actual: Some("43".into()) },
+///     ]
+/// );
+/// assert_eq!(expected.check(None), vec![UploadMismatch::Missing]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UploadExpectation {
+    /// The object must be at least this many bytes
+    pub min_size: Option<u64>,
+    /// The object must be at most this many bytes
+    pub max_size: Option<u64>,
+    pub content_type: Option<ContentTypeCondition>,
+    /// Base64 checksums by algorithm (`crc32`, `sha256`...)
+    pub checksums: BTreeMap<String, String>,
+    /// User metadata, names are lowercased
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl UploadExpectation {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expect the size allowed by a presigned post's condition.
+    #[inline]
+    pub fn content_length(mut self, condition: impl Into<ContentLengthCondition>) -> Self {
+        let (min, max) = condition.into().range();
+        self.min_size = Some(min.max(0) as u64);
+        self.max_size = Some(max.max(0) as u64);
+        self
+    }
+
+    #[inline]
+    pub fn min_size(mut self, size: u64) -> Self {
+        self.min_size = Some(size);
+        self
+    }
+
+    #[inline]
+    pub fn max_size(mut self, size: u64) -> Self {
+        self.max_size = Some(size);
+        self
+    }
+
+    #[inline]
+    pub fn content_type(mut self, content_type: impl Into<ContentTypeCondition>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    /// Expect a checksum, e.g. `("sha256", "<base64 digest>")`. S3 only returns checksums of
+    /// objects uploaded with one.
+    #[inline]
+    pub fn checksum(mut self, algorithm: impl Into<String>, base64: impl Into<String>) -> Self {
+        self.checksums
+            .insert(algorithm.into().to_lowercase(), base64.into());
+        self
+    }
+
+    #[inline]
+    pub fn metadata(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata
+            .insert(name.into().to_lowercase(), value.into());
+        self
+    }
+
+    /// Mismatches between the expectation and the head of the object, `None` when missing.
+    #[inline]
+    pub fn check(&self, head: Option<&HeadObjectOutput>) -> Vec<UploadMismatch> {
+        let head = match head {
+            Some(head) => head,
+            None => return vec![UploadMismatch::Missing],
+        };
+        let mut mismatches = vec![];
+
+        let size = head.content_length.unwrap_or(0);
+        let min = self.min_size.unwrap_or(0);
+        let max = self.max_size.unwrap_or(u64::MAX);
+        if size < min || size > max {
+            mismatches.push(UploadMismatch::SizeOutOfRange {
+                min,
+                max,
+                actual: size,
+            });
+        }
+
+        if let Some(expected) = &self.content_type {
+            let actual = head.content_type.as_deref();
+            let matches = match (expected, actual) {
+                (ContentTypeCondition::Exact(expected), Some(actual)) => expected == actual,
+                (ContentTypeCondition::StartsWith(prefix), Some(actual)) => {
+                    actual.starts_with(prefix.as_str())
+                }
+                // Resolved against the key by `S3::verify_client_upload`, any type otherwise
+                (ContentTypeCondition::Guess, Some(_)) => true,
+                (_, None) => false,
+            };
+            if !matches {
+                mismatches.push(UploadMismatch::ContentType {
+                    expected: expected.clone(),
+                    actual: actual.map(String::from),
+                });
+            }
+        }
+
+        for (algorithm, expected) in &self.checksums {
+            let actual = head.checksums.get(algorithm);
+            if actual != Some(expected) {
+                mismatches.push(UploadMismatch::Checksum {
+                    algorithm: algorithm.clone(),
+                    expected: expected.clone(),
+                    actual: actual.cloned(),
+                });
+            }
+        }
+
+        for (name, expected) in &self.metadata {
+            let actual = head.metadata.get(name);
+            if actual != Some(expected) {
+                mismatches.push(UploadMismatch::Metadata {
+                    name: name.clone(),
+                    expected: expected.clone(),
+                    actual: actual.cloned(),
+                });
+            }
+        }
+
+        mismatches
+    }
+}
+
+/// Difference between an uploaded object and its `UploadExpectation`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UploadMismatch {
+    /// The object was not uploaded
+    Missing,
+    SizeOutOfRange {
+        min: u64,
+        max: u64,
+        actual: u64,
+    },
+    ContentType {
+        expected: ContentTypeCondition,
+        actual: Option<String>,
+    },
+    Checksum {
+        algorithm: String,
+        expected: String,
+        actual: Option<String>,
+    },
+    Metadata {
+        name: String,
+        expected: String,
+        actual: Option<String>,
+    },
+}
+
+/// Result of `verify_client_upload`.
+#[derive(Debug, Clone)]
+pub struct UploadVerification {
+    /// Head of the object, `None` if it was not uploaded
+    pub head: Option<HeadObjectOutput>,
+    pub mismatches: Vec<UploadMismatch>,
+}
+
+impl UploadVerification {
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+impl S3 {
+    /// Head an object uploaded by a client, e.g. with a presigned post, and check it against
+    /// what was authorized. A missing object is reported as `UploadMismatch::Missing`.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::{UploadExpectation, S3};
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// let expected = UploadExpectation::new().content_length(10485760).content_type("image/png");
+    /// let verification = s3.verify_client_upload("avatars/42.png", &expected).await?;
+    /// if !verification.is_ok() {
+    ///     s3.delete_object("avatars/42.png").await?;
+    ///     eprintln!("rejected upload: {:?}", verification.mismatches);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn verify_client_upload(
+        &self,
+        key: impl TryInto<S3Key, Error = impl Into<Error>>,
+        expected: &UploadExpectation,
+    ) -> Result<UploadVerification, Error> {
+        let key = try_key(key)?;
+        let mut expected = expected.clone();
+        expected.content_type = expected.content_type.map(|c| c.resolve(&key));
+        let options = RequestOptions::new().checksum_mode(!expected.checksums.is_empty());
+        let head = self.head_object_with_options(&key, &options).await?;
+        Ok(UploadVerification {
+            mismatches: expected.check(head.as_ref()),
+            head,
+        })
+    }
+}
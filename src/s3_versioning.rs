@@ -0,0 +1,402 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use serde::de::IgnoredAny;
+
+use crate::error::Error;
+use crate::{CommonPrefix, ListFuture, ListRequest, RequestOptions, S3};
+
+/// Versioning state of a bucket. Buckets are unversioned until versioning is enabled, and can
+/// only be suspended afterwards.
+///
+/// Ref: https://docs.aws.amazon.com/AmazonS3/latest/userguide/Versioning.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VersioningStatus {
+    Enabled,
+    /// New objects get the `null` version, existing versions are kept
+    Suspended,
+}
+
+impl VersioningStatus {
+    #[inline]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Enabled => "Enabled",
+            Self::Suspended => "Suspended",
+        }
+    }
+
+    /// Parse a GetBucketVersioning response, `None` for a bucket never versioned.
+    ///
+    /// Example:
+    /// ```rust
+    /// use simple_aws_s3::VersioningStatus;
+    ///
+    /// let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+    /// <VersioningConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+    ///   <Status>Enabled</Status>
+    /// </VersioningConfiguration>"#;
+    /// assert_eq!(VersioningStatus::from_xml(body).unwrap(), Some(VersioningStatus::Enabled));
+    ///
+    /// let body = r#"<VersioningConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/"/>"#;
+    /// assert_eq!(VersioningStatus::from_xml(body).unwrap(), None);
+    ///
+    /// assert!(VersioningStatus::Suspended.to_xml().contains("<Status>Suspended</Status>"));
+    /// ```
+    #[inline]
+    pub fn from_xml(body: &str) -> Result<Option<Self>, Error> {
+        let config: VersioningConfiguration = serde_xml_rs::from_str(body)?;
+        config.status.map(|status| status.parse()).transpose()
+    }
+
+    #[inline]
+    pub fn to_xml(&self) -> String {
+        format!(
+            r#"<VersioningConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Status>{}</Status></VersioningConfiguration>"#,
+            self.as_str()
+        )
+    }
+}
+
+impl fmt::Display for VersioningStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for VersioningStatus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Enabled" => Ok(Self::Enabled),
+            "Suspended" => Ok(Self::Suspended),
+            _ => Err(Error::InvalidInput(format!(
+                "Unknown versioning status {:?}",
+                s
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct VersioningConfiguration {
+    #[serde(default)]
+    status: Option<String>,
+}
+
+impl S3 {
+    /// Get the versioning state of the bucket, `None` if it was never versioned.
+    #[inline]
+    pub async fn get_bucket_versioning(&self) -> Result<Option<VersioningStatus>, Error> {
+        let req = self.prepare_request(
+            Method::GET,
+            None,
+            &[("versioning", "")],
+            &RequestOptions::default(),
+        )?;
+        let res = self.execute(req).await?;
+        VersioningStatus::from_xml(&res.text().await?)
+    }
+
+    /// Enable or suspend the versioning of the bucket.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use simple_aws_s3::{VersioningStatus, S3};
+    ///
+    /// # async fn run(s3: S3) -> Result<(), simple_aws_s3::error::Error> {
+    /// s3.put_bucket_versioning(VersioningStatus::Enabled).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn put_bucket_versioning(&self, status: VersioningStatus) -> Result<(), Error> {
+        let req =
+            self.prepare_xml_request(Method::PUT, None, &[("versioning", "")], status.to_xml())?;
+        self.execute(req).await?;
+        Ok(())
+    }
+}
+
+/// Query params of a ListObjectVersions request.
+///
+/// Ref: https://docs.aws.amazon.com/AmazonS3/latest/API/API_ListObjectVersions.html
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListVersionsOptions {
+    pub prefix: Option<String>,
+    pub delimiter: Option<String>,
+    /// Maximum number of versions and delete markers returned in a page, 1000 at most
+    pub max_keys: Option<u32>,
+    /// Key of the page to list, returned as `next_key_marker` by the previous page
+    pub key_marker: Option<String>,
+    /// Version of the page to list, returned as `next_version_id_marker` by the previous page
+    pub version_id_marker: Option<String>,
+}
+
+impl ListVersionsOptions {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    #[inline]
+    pub fn delimiter(mut self, delimiter: impl Into<String>) -> Self {
+        self.delimiter = Some(delimiter.into());
+        self
+    }
+
+    #[inline]
+    pub fn max_keys(mut self, max_keys: u32) -> Self {
+        self.max_keys = Some(max_keys);
+        self
+    }
+
+    #[inline]
+    pub(crate) fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = vec![("versions", String::new())];
+        if let Some(delimiter) = &self.delimiter {
+            query.push(("delimiter", delimiter.clone()));
+        }
+        if let Some(marker) = &self.key_marker {
+            query.push(("key-marker", marker.clone()));
+        }
+        if let Some(max_keys) = self.max_keys {
+            query.push(("max-keys", max_keys.to_string()));
+        }
+        if let Some(prefix) = &self.prefix {
+            query.push(("prefix", prefix.clone()));
+        }
+        if let Some(marker) = &self.version_id_marker {
+            query.push(("version-id-marker", marker.clone()));
+        }
+        query
+    }
+}
+
+/// Version of an object, or delete marker, of a ListObjectVersions response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectVersion {
+    pub key: String,
+    /// `null` for the version written while versioning was not enabled
+    pub version_id: String,
+    pub is_latest: bool,
+    pub last_modified: DateTime<Utc>,
+    /// The version is a delete marker, without content
+    pub is_delete_marker: bool,
+    pub etag: Option<String>,
+    pub size: Option<u64>,
+    pub storage_class: Option<String>,
+}
+
+/// A page of a ListObjectVersions response.
+///
+/// Example:
+/// ```rust
+/// use simple_aws_s3::ListVersionsOutput;
+///
+/// let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <ListVersionsResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+///   <Name>examplebucket</Name>
+///   <Prefix>photos/</Prefix>
+///   <KeyMarker></KeyMarker>
+///   <VersionIdMarker></VersionIdMarker>
+///   <NextKeyMarker>photos/b.png</NextKeyMarker>
+///   <NextVersionIdMarker>3/L4kqtJlcpXroDTDmJ+rmSpXd3dIbrHY</NextVersionIdMarker>
+///   <MaxKeys>3</MaxKeys>
+///   <IsTruncated>true</IsTruncated>
+///   <DeleteMarker>
+///     <Key>photos/a.png</Key>
+///     <VersionId>null</VersionId>
+///     <IsLatest>true</IsLatest>
+///     <LastModified>2021-06-02T10:00:00.000Z</LastModified>
+///   </DeleteMarker>
+///   <Version>
+///     <Key>photos/a.png</Key>
+///     <VersionId>3/L4kqtJl40Nr8X8gdRQBpUMLUo</VersionId>
+///     <IsLatest>false</IsLatest>
+///     <LastModified>2021-06-01T10:00:00.000Z</LastModified>
+///     <ETag>"fba9dede5f27731c9771645a39863328"</ETag>
+///     <Size>434234</Size>
+///     <StorageClass>STANDARD</StorageClass>
+///   </Version>
+///   <DeleteMarker>
+///     <Key>photos/b.png</Key>
+///     <VersionId>3/L4kqtJlcpXroDTDmJ+rmSpXd3dIbrHY</VersionId>
+///     <IsLatest>true</IsLatest>
+///     <LastModified>2021-06-02T11:00:00.000Z</LastModified>
+///   </DeleteMarker>
+/// </ListVersionsResult>"#;
+///
+/// let output = ListVersionsOutput::from_xml(body).unwrap();
+/// assert_eq!(output.versions.len(), 3);
+/// assert!(output.versions[0].is_delete_marker);
+/// assert_eq!(output.versions[1].size, Some(434234));
+/// assert_eq!(output.versions[2].key, "photos/b.png");
+/// assert_eq!(output.next_key_marker.as_deref(), Some("photos/b.png"));
+/// assert!(output.is_truncated);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListVersionsOutput {
+    pub name: String,
+    pub prefix: Option<String>,
+    pub key_marker: Option<String>,
+    pub version_id_marker: Option<String>,
+    pub next_key_marker: Option<String>,
+    pub next_version_id_marker: Option<String>,
+    pub is_truncated: bool,
+    /// Versions and delete markers, in the order of the listing
+    pub versions: Vec<ObjectVersion>,
+    pub common_prefixes: Vec<CommonPrefix>,
+}
+
+impl ListVersionsOutput {
+    #[inline]
+    pub fn from_xml(body: &str) -> Result<Self, Error> {
+        let result: ListVersionsResultXml = serde_xml_rs::from_str(body)?;
+        let mut output = Self::default();
+        let non_empty = |value: String| Some(value).filter(|value| !value.is_empty());
+        for entry in result.entries {
+            match entry {
+                ListVersionsEntryXml::Name(name) => output.name = name,
+                ListVersionsEntryXml::Prefix(prefix) => output.prefix = non_empty(prefix),
+                ListVersionsEntryXml::KeyMarker(marker) => output.key_marker = non_empty(marker),
+                ListVersionsEntryXml::VersionIdMarker(marker) => {
+                    output.version_id_marker = non_empty(marker)
+                }
+                ListVersionsEntryXml::NextKeyMarker(marker) => {
+                    output.next_key_marker = non_empty(marker)
+                }
+                ListVersionsEntryXml::NextVersionIdMarker(marker) => {
+                    output.next_version_id_marker = non_empty(marker)
+                }
+                ListVersionsEntryXml::IsTruncated(truncated) => output.is_truncated = truncated,
+                ListVersionsEntryXml::Version(version) => {
+                    output.versions.push(version.into_version(false))
+                }
+                ListVersionsEntryXml::DeleteMarker(marker) => {
+                    output.versions.push(marker.into_version(true))
+                }
+                ListVersionsEntryXml::CommonPrefixes(prefix) => output.common_prefixes.push(prefix),
+                ListVersionsEntryXml::MaxKeys(_)
+                | ListVersionsEntryXml::Delimiter(_)
+                | ListVersionsEntryXml::EncodingType(_) => {}
+            }
+        }
+        Ok(output)
+    }
+
+    /// Options of the page after this one, listed with `options`, or `None` for the last page.
+    #[inline]
+    pub fn next_page(&self, options: &ListVersionsOptions) -> Option<ListVersionsOptions> {
+        if !self.is_truncated {
+            return None;
+        }
+        let mut next = options.clone();
+        next.key_marker = Some(self.next_key_marker.clone()?);
+        next.version_id_marker = self.next_version_id_marker.clone();
+        Some(next)
+    }
+}
+
+/// Elements of a ListObjectVersions response, where versions and delete markers interleave.
+#[derive(Debug, Deserialize)]
+struct ListVersionsResultXml {
+    #[serde(rename = "$value", default)]
+    entries: Vec<ListVersionsEntryXml>,
+}
+
+#[derive(Debug, Deserialize)]
+enum ListVersionsEntryXml {
+    Name(String),
+    Prefix(String),
+    KeyMarker(String),
+    VersionIdMarker(String),
+    NextKeyMarker(String),
+    NextVersionIdMarker(String),
+    MaxKeys(IgnoredAny),
+    Delimiter(IgnoredAny),
+    EncodingType(IgnoredAny),
+    IsTruncated(bool),
+    Version(ObjectVersionXml),
+    DeleteMarker(ObjectVersionXml),
+    CommonPrefixes(CommonPrefix),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ObjectVersionXml {
+    key: String,
+    version_id: String,
+    is_latest: bool,
+    last_modified: DateTime<Utc>,
+    #[serde(rename = "ETag", default)]
+    etag: Option<String>,
+    #[serde(default)]
+    size: Option<u64>,
+    #[serde(default)]
+    storage_class: Option<String>,
+}
+
+impl ObjectVersionXml {
+    #[inline]
+    fn into_version(self, is_delete_marker: bool) -> ObjectVersion {
+        ObjectVersion {
+            key: self.key,
+            version_id: self.version_id,
+            is_latest: self.is_latest,
+            last_modified: self.last_modified,
+            is_delete_marker,
+            etag: self.etag,
+            size: self.size,
+            storage_class: self.storage_class,
+        }
+    }
+}
+
+impl S3 {
+    /// List a page of the versions and delete markers of the objects of the bucket.
+    #[inline]
+    pub async fn list_object_versions(
+        &self,
+        options: &ListVersionsOptions,
+    ) -> Result<ListVersionsOutput, Error> {
+        let query = options.query();
+        let query = query
+            .iter()
+            .map(|(name, value)| (*name, value.as_str()))
+            .collect::<Vec<_>>();
+        let req = self.prepare_request(Method::GET, None, &query, &RequestOptions::default())?;
+        let res = self.execute(req).await?;
+        ListVersionsOutput::from_xml(&res.text().await?)
+    }
+}
+
+impl ListRequest for ListVersionsOptions {
+    type Page = ListVersionsOutput;
+    type Item = ObjectVersion;
+
+    #[inline]
+    fn fetch<'a>(&'a self, s3: &'a S3) -> ListFuture<'a, Self::Page> {
+        Box::pin(s3.list_object_versions(self))
+    }
+
+    #[inline]
+    fn next_request(&self, page: &Self::Page) -> Option<Self> {
+        page.next_page(self)
+    }
+
+    #[inline]
+    fn items(page: Self::Page) -> Vec<Self::Item> {
+        page.versions
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+use futures_util::stream::{self, Stream};
+
+use crate::error::Error;
+use crate::{ListObjectsOptions, ObjectSummary, S3};
+
+/// Change of an object under a watched prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrefixChange {
+    Created(ObjectSummary),
+    /// The ETag or the last modified date of the object changed
+    Modified(ObjectSummary),
+    /// The object was deleted, holds its last known summary
+    Deleted(ObjectSummary),
+}
+
+impl PrefixChange {
+    #[inline]
+    pub fn key(&self) -> &str {
+        match self {
+            Self::Created(o) | Self::Modified(o) | Self::Deleted(o) => &o.key,
+        }
+    }
+}
+
+struct WatchState {
+    s3: S3,
+    prefix: String,
+    interval: Duration,
+    /// Objects of the last successful listing
+    snapshot: Option<HashMap<String, ObjectSummary>>,
+    pending: VecDeque<PrefixChange>,
+    polled: bool,
+}
+
+impl S3 {
+    /// Watch the objects under `prefix` by listing them every `interval`.
+    ///
+    /// The first listing is the baseline, then every change between two listings is yielded. A
+    /// failed listing yields its error and is retried at the next interval. This is meant for
+    /// environments where bucket notifications can't be set up.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use futures_util::StreamExt;
+    /// use simple_aws_s3::{PrefixChange, S3};
+    ///
+    /// # async fn run(s3: S3) {
+    /// let changes = s3.watch_prefix("uploads/", Duration::from_secs(30));
+    /// futures_util::pin_mut!(changes);
+    /// while let Some(change) = changes.next().await {
+    ///     match change {
+    ///         Ok(PrefixChange::Created(object)) => println!("new object {}", object.key),
+    ///         Ok(change) => println!("{:?}", change),
+    ///         Err(e) => eprintln!("listing failed: {}", e),
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn watch_prefix(
+        &self,
+        prefix: impl Into<String>,
+        interval: Duration,
+    ) -> impl Stream<Item = Result<PrefixChange, Error>> + Send + 'static {
+        let state = WatchState {
+            s3: self.clone(),
+            prefix: prefix.into(),
+            interval,
+            snapshot: None,
+            pending: VecDeque::new(),
+            polled: false,
+        };
+
+        stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(change) = state.pending.pop_front() {
+                    return Some((Ok(change), state));
+                }
+                if state.polled {
+                    tokio::time::sleep(state.interval).await;
+                }
+                state.polled = true;
+
+                let objects = match list_all(&state.s3, &state.prefix).await {
+                    Ok(objects) => objects,
+                    Err(e) => return Some((Err(e), state)),
+                };
+                if let Some(snapshot) = &state.snapshot {
+                    state.pending = diff(snapshot, &objects);
+                }
+                state.snapshot = Some(objects);
+            }
+        })
+    }
+}
+
+#[inline]
+async fn list_all(s3: &S3, prefix: &str) -> Result<HashMap<String, ObjectSummary>, Error> {
+    let options = ListObjectsOptions::new().prefix(prefix);
+    let objects = s3.list_all_objects_v2(&options).await?;
+    Ok(objects.into_iter().map(|o| (o.key.clone(), o)).collect())
+}
+
+#[inline]
+fn diff(
+    before: &HashMap<String, ObjectSummary>,
+    after: &HashMap<String, ObjectSummary>,
+) -> VecDeque<PrefixChange> {
+    let mut changes = vec![];
+    for (key, object) in after {
+        match before.get(key) {
+            None => changes.push(PrefixChange::Created(object.clone())),
+            Some(old) if old.etag != object.etag || old.last_modified != object.last_modified => {
+                changes.push(PrefixChange::Modified(object.clone()))
+            }
+            Some(_) => {}
+        }
+    }
+    for (key, object) in before {
+        if !after.contains_key(key) {
+            changes.push(PrefixChange::Deleted(object.clone()));
+        }
+    }
+    changes.sort_by(|a, b| a.key().cmp(b.key()));
+    changes.into()
+}